@@ -0,0 +1,139 @@
+use std::f64::consts::PI;
+
+use crate::image::Image;
+use crate::vec3::{RandomUnitDisk, Vec3};
+
+// Shape of a lens opening, the shape of out of focus highlights (bokeh)
+#[derive(Clone)]
+pub enum Aperture {
+    Circular,
+    // Opening of a diaphragm with straight blades
+    Polygonal(AperturePolygon),
+    // Opening of any shape, from an image
+    Mask(ApertureMask),
+}
+
+impl Aperture {
+    // Uniformly distributed point of the opening, within the unit disk.
+    pub fn sample(&self) -> (f64, f64) {
+        match self {
+            Self::Circular => {
+                let p = Vec3::random_unit_disk();
+                (p.x(), p.y())
+            }
+            Self::Polygonal(polygon) => polygon.sample(),
+            Self::Mask(mask) => mask.sample(),
+        }
+    }
+}
+
+// Regular polygon inscribed in the unit circle, turned by rotation degrees
+#[derive(Clone)]
+pub struct AperturePolygon {
+    blades: usize,
+    rotation: f64,
+}
+
+impl AperturePolygon {
+    pub fn new(blades: usize, rotation: f64) -> anyhow::Result<Self> {
+        if blades < 3 {
+            anyhow::bail!("aperture polygon with {blades} blades, at least 3 are needed");
+        }
+
+        Ok(Self { blades, rotation })
+    }
+
+    fn sample(&self) -> (f64, f64) {
+        // All the triangles from the center to each side have the same area
+        let side = fastrand::usize(..self.blades) as f64;
+        let angle = |i: f64| self.rotation.to_radians() + 2.0 * PI * i / self.blades as f64;
+        let (a, b) = (angle(side), angle(side + 1.0));
+
+        // Uniform point of the triangle by folding the unit square in two
+        let (mut u, mut v) = (fastrand::f64(), fastrand::f64());
+        if u + v > 1.0 {
+            (u, v) = (1.0 - u, 1.0 - v);
+        }
+        (u * a.cos() + v * b.cos(), u * a.sin() + v * b.sin())
+    }
+}
+
+// Aperture whose transmission is the luminance of an image, fit in the unit disk.
+#[derive(Clone)]
+pub struct ApertureMask {
+    width: usize,
+    height: usize,
+    cdf: Vec<f64>, // Cumulative luminance of the pixels, rows from the top
+}
+
+impl ApertureMask {
+    pub fn new(image: &Image) -> anyhow::Result<Self> {
+        let (width, height) = (image.width(), image.height());
+        let mut total = 0.0;
+        let mut cdf = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                total += image.luminance(x, y).max(0.0);
+                cdf.push(total);
+            }
+        }
+        if total <= 0.0 {
+            anyhow::bail!("aperture mask without any opening");
+        }
+
+        Ok(Self { width, height, cdf })
+    }
+
+    // Pixel picked in proportion to its luminance, and a uniform point within it.
+    fn sample(&self) -> (f64, f64) {
+        let total = self.cdf[self.cdf.len() - 1];
+        let target = fastrand::f64() * total;
+        let i = self
+            .cdf
+            .partition_point(|c| *c <= target)
+            .min(self.cdf.len() - 1);
+        let (x, y) = (i % self.width, i / self.width);
+
+        // Square around the unit disk, as the diagonal would shrink the bokeh too much
+        let size = self.width.max(self.height) as f64;
+        let px = (x as f64 + fastrand::f64() - self.width as f64 / 2.0) / size * 2.0;
+        let py = (self.height as f64 / 2.0 - y as f64 - fastrand::f64()) / size * 2.0;
+        (px, py)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygonal() {
+        // A square with its corners on the axes, |x| + |y| <= 1
+        let aperture = Aperture::Polygonal(AperturePolygon::new(4, 0.0).unwrap());
+        for _ in 0..1000 {
+            let (x, y) = aperture.sample();
+            assert!(x.abs() + y.abs() <= 1.0 + 1e-9, "({x}, {y})");
+        }
+
+        // Two blades don't close around anything
+        assert!(AperturePolygon::new(2, 0.0).is_err());
+        assert!(AperturePolygon::new(0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_mask() {
+        // Only the top right pixel of a 2x2 mask is open
+        let image = Image::read(&b"P2 2 2 1 0 1 0 0"[..]).unwrap();
+        let aperture = Aperture::Mask(ApertureMask::new(&image).unwrap());
+        for _ in 0..1000 {
+            let (x, y) = aperture.sample();
+            assert!(
+                (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y),
+                "({x}, {y})"
+            );
+        }
+
+        let closed = Image::read(&b"P2 1 1 1 0"[..]).unwrap();
+        assert!(ApertureMask::new(&closed).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::{
 };
 
 use crate::color::Color;
+use crate::film::CropWindow;
 
 #[derive(Debug)]
 pub struct Buffer {
@@ -52,6 +53,78 @@ impl Buffer {
         self.data.lock().unwrap()[height] = line;
     }
 
+    // Copy buffer over this one, with its top left corner at (x, y) from the top left corner,
+    // like a crop window rendered into the full image.
+    pub fn paste(&mut self, buffer: &Buffer, x: usize, y: usize) -> anyhow::Result<()> {
+        let fits = |start: usize, size: usize, bound: usize| {
+            start.checked_add(size).is_some_and(|end| end <= bound)
+        };
+        if !fits(x, buffer.width, self.width) || !fits(y, buffer.height, self.height) {
+            anyhow::bail!(
+                "{}x{} buffer at ({x}, {y}) outside of the {}x{} one",
+                buffer.width,
+                buffer.height,
+                self.width,
+                self.height
+            );
+        }
+
+        let data = self.data.get_mut().unwrap();
+        let lines = buffer.data.lock().unwrap();
+        for (height, line) in lines.iter().enumerate() {
+            let line_height = self.height - y - buffer.height + height;
+            data[line_height].resize(self.width, Color::default());
+            // Lines never pushed are black, as in crop()
+            let pixels = line
+                .iter()
+                .copied()
+                .chain(std::iter::repeat(Color::default()));
+            for (pixel, color) in data[line_height][x..x + buffer.width]
+                .iter_mut()
+                .zip(pixels)
+            {
+                *pixel = color;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Copy of the part of this buffer within crop, from its top left corner
+    pub fn crop(&self, crop: &CropWindow) -> anyhow::Result<Buffer> {
+        if crop.x.end > self.width || crop.y.end > self.height {
+            anyhow::bail!(
+                "crop window {crop:?} outside of the {}x{} buffer",
+                self.width,
+                self.height
+            );
+        }
+
+        let mut buffer = Buffer::new(crop.x.len(), crop.y.len());
+        let data = self.data.lock().unwrap();
+        let lines = buffer.data.get_mut().unwrap();
+        for (height, line) in lines.iter_mut().enumerate() {
+            let line_height = self.height - crop.y.end + height;
+            // Lines never pushed nor pasted over are black
+            *line = data[line_height]
+                .iter()
+                .copied()
+                .chain(std::iter::repeat(Color::default()))
+                .skip(crop.x.start)
+                .take(crop.x.len())
+                .collect();
+        }
+
+        Ok(buffer)
+    }
+
+    // Color of the pixel at (x, y) from the top left corner, black if never drawn
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let data = self.data.lock().unwrap();
+        let line = &data[self.height - 1 - y];
+        line.get(x).copied().unwrap_or_default()
+    }
+
     pub fn save(&self, filename: &str) -> anyhow::Result<()> {
         print!("Encoding colors... ");
         std::io::stdout().flush().unwrap();
@@ -95,3 +168,82 @@ impl Buffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Buffer of the given lines, from its bottom
+    fn buffer(lines: Vec<Vec<f64>>) -> Buffer {
+        let buffer = Buffer::new(lines[0].len(), lines.len());
+        for line in lines {
+            let (height, _) = buffer.get_line().unwrap();
+            let line = line.into_iter().map(|v| Color::new(v, v, v)).collect();
+            buffer.push_line(height, line);
+        }
+        buffer
+    }
+
+    fn lines(buffer: &Buffer) -> Vec<Vec<f64>> {
+        let data = buffer.data.lock().unwrap();
+        data.iter()
+            .map(|line| line.iter().map(|color| color.x()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_paste() {
+        let mut full = Buffer::new(3, 3);
+        full.paste(&buffer(vec![vec![1.0, 2.0]]), 1, 0).unwrap();
+        assert_eq!(vec![vec![], vec![], vec![0.0, 1.0, 2.0]], lines(&full));
+
+        // Past the right or bottom edge
+        let tile = buffer(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert!(full.paste(&tile, 2, 0).is_err());
+        assert!(full.paste(&tile, 0, 2).is_err());
+        assert!(full.paste(&tile, usize::MAX, 0).is_err());
+        full.paste(&tile, 1, 1).unwrap();
+        let crop = full.crop(&CropWindow { x: 0..3, y: 1..2 }).unwrap();
+        assert_eq!(vec![vec![0.0, 3.0, 4.0]], lines(&crop));
+    }
+
+    #[test]
+    fn test_paste_crop() {
+        // Values of the pixels from the top left corner: 1 2 3, 4 5 6, 7 8 9
+        let full = buffer(vec![
+            vec![7.0, 8.0, 9.0],
+            vec![4.0, 5.0, 6.0],
+            vec![1.0, 2.0, 3.0],
+        ]);
+        let window = CropWindow { x: 1..3, y: 1..3 };
+        let crop = full.crop(&window).unwrap();
+        assert_eq!(vec![vec![8.0, 9.0], vec![5.0, 6.0]], lines(&crop));
+
+        // Back where it was cropped, into an empty image then out again
+        let mut pasted = Buffer::new(3, 3);
+        pasted.paste(&crop, 1, 1).unwrap();
+        assert_eq!(5.0, pasted.pixel(1, 1).x());
+        assert_eq!(0.0, pasted.pixel(0, 0).x());
+        assert_eq!(lines(&crop), lines(&pasted.crop(&window).unwrap()));
+
+        // A buffer with lines never drawn pastes black
+        let mut pasted = buffer(vec![vec![1.0; 3]; 3]);
+        pasted.paste(&Buffer::new(2, 2), 0, 0).unwrap();
+        let expected = vec![
+            vec![1.0, 1.0, 1.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        assert_eq!(expected, lines(&pasted));
+    }
+
+    #[test]
+    fn test_crop() {
+        // Values of the pixels from the top left corner: 1 2 3, 4 5 6
+        let full = buffer(vec![vec![4.0, 5.0, 6.0], vec![1.0, 2.0, 3.0]]);
+        let crop = full.crop(&CropWindow { x: 1..3, y: 0..1 }).unwrap();
+        assert_eq!(vec![vec![2.0, 3.0]], lines(&crop));
+        assert!(full.crop(&CropWindow { x: 1..4, y: 0..1 }).is_err());
+        assert!(full.crop(&CropWindow { x: 0..1, y: 0..3 }).is_err());
+    }
+}
@@ -1,68 +1,52 @@
+use std::ops::Range;
+
 use crate::point3::Point3;
 use crate::ray::Ray;
-use crate::vec3::{Cross, MulAdd, RandomUnitDisk, Unit, Vec3};
+use crate::vec3::{Cross, Unit, Vec3};
 
-pub struct Camera {
-    origin: Point3,
-    lower_left_corner: Point3,
-    horizontal: Vec3,
-    vertical: Vec3,
-    u: Vec3,
-    v: Vec3,
-    lens_radius: f64,
-}
+// Projection of the film onto rays of the scene
+pub trait Camera: Send + Sync {
+    // Ray through the film at (s, t) in [0, 1], from the bottom left corner. None where the
+    // projection doesn't cover the film, like around the image circle of a fisheye.
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray>;
 
-impl Camera {
-    pub fn new(
-        look_from: Point3,
-        look_at: Point3,
-        up: Vec3,
-        vertical_fov: f64, // Vertical field-of-view in degrees
-        aspect_ratio: f64,
-        aperture: f64,
-        focus_distance: f64,
-    ) -> Self {
-        let theta = vertical_fov.to_radians();
-        let h = (theta / 2_f64).tan();
-        let viewport_height: f64 = 2_f64 * h;
-        let viewport_width: f64 = aspect_ratio * viewport_height;
+    // Scale from the radiance of the scene to pixel values.
+    fn exposure(&self) -> f64 {
+        1.0
+    }
 
-        let w = (look_from - look_at).unit();
-        let u = up.cross(w).unit();
-        let v = w.cross(u);
+    // Keep the shutter open from open to close, see Shutter.
+    fn with_shutter(self, open: f64, close: f64) -> Self
+    where
+        Self: Sized;
+}
 
-        let origin: Point3 = look_from;
-        let horizontal: Vec3 = focus_distance * viewport_width * u;
-        let vertical: Vec3 = focus_distance * viewport_height * v;
-        let lower_left_corner: Vec3 = origin - (horizontal + vertical) / 2_f64 - focus_distance * w;
+// Orthonormal basis of a camera at look_from looking at look_at: u points right, v up and w
+// backwards, away from the scene.
+pub fn look_at_basis(look_from: &Point3, look_at: &Point3, up: &Vec3) -> (Vec3, Vec3, Vec3) {
+    let w = (look_from - look_at).unit();
+    let u = up.cross(w).unit();
+    let v = w.cross(u);
 
-        let lens_radius = aperture / 2_f64;
+    (u, v, w)
+}
 
+// Interval the shutter is open, to capture moving objects with motion blur. By default it
+// captures a single instant, time 0.
+#[derive(Clone, Default)]
+pub struct Shutter {
+    interval: Range<f64>, // Open and close times
+}
+
+impl Shutter {
+    pub fn new(open: f64, close: f64) -> Self {
         Self {
-            origin,
-            lower_left_corner,
-            horizontal,
-            vertical,
-            u,
-            v,
-            lens_radius,
+            interval: open..close,
         }
     }
-    pub fn get_ray(&self, s: f64, t: f64) -> Ray {
-        let rd = self.lens_radius * Vec3::random_unit_disk();
-
-        // ray_origin = self.origin + offset
-        // offset = self.u * rd.x() + self.v * rd.y()
-        // ray_origin = self.origin + self.u * rd.x() + self.v * rd.y()
-        //
-        let origin = self.u.mul_add(rd.x(), self.v.mul_add(rd.y(), self.origin));
-
-        // direction: self.lower_left_corner + s * self.horizontal + t * self.vertical - origin
-        //
-        let direction = self
-            .horizontal
-            .mul_add(s, self.vertical.mul_add(t, self.lower_left_corner - origin));
 
-        Ray::new(origin, direction)
+    // Uniformly distributed instant while the shutter is open
+    pub fn sample(&self) -> f64 {
+        self.interval.start + fastrand::f64() * (self.interval.end - self.interval.start)
     }
 }
@@ -0,0 +1,316 @@
+use std::ops::{Add, Mul, RangeInclusive};
+
+use anyhow::bail;
+
+use crate::aperture::Aperture;
+use crate::camera::Camera;
+use crate::point3::Point3;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::transform::Transform;
+use crate::vec3::{Length, Vec3};
+
+// Camera settings at a frame of an animation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe {
+    pub frame: usize,
+    pub look_from: Point3,
+    pub look_at: Point3,
+    pub vertical_fov: f64, // Degrees
+    pub focus_distance: f64,
+}
+
+// Settings of the lens kept all along a path, see the builders of ThinLensCamera
+#[derive(Clone)]
+pub struct LensSettings {
+    pub aperture: Aperture, // Shape of the opening
+    pub cat_eye: f64,
+    pub shift: (f64, f64),        // Horizontal and vertical
+    pub tilt: Option<(f64, f64)>, // Tilt and swing, in degrees
+    pub shutter: (f64, f64),      // Open and close times, within each frame
+    pub exposure: f64,
+}
+
+impl Default for LensSettings {
+    fn default() -> Self {
+        Self {
+            aperture: Aperture::Circular,
+            cat_eye: 0.0,
+            shift: (0.0, 0.0),
+            tilt: None,
+            shutter: (0.0, 0.0),
+            exposure: 1.0,
+        }
+    }
+}
+
+// How the camera moves in between keyframes
+pub enum Interpolation {
+    Linear,
+    CatmullRom, // Smooth spline through the keyframes
+}
+
+impl Interpolation {
+    // Weights of the keyframes before, at the start, at the end and after the segment, at u in
+    // [0, 1] along it. frames are those of the keyframes, the ones before and after may repeat
+    // the ends of the segment.
+    fn weights(&self, u: f64, frames: [f64; 4]) -> [f64; 4] {
+        match self {
+            Self::Linear => [0.0, 1.0 - u, u, 0.0],
+            Self::CatmullRom => {
+                // Hermite spline, with the tangents at the ends of the segment from the keyframes
+                // on both sides, scaled by the spacing of the frames so the speed doesn't jump at
+                // the keyframes when they're unevenly spaced.
+                let (u2, u3) = (u * u, u * u * u);
+                let (h00, h10) = (2.0 * u3 - 3.0 * u2 + 1.0, u3 - 2.0 * u2 + u);
+                let (h01, h11) = (-2.0 * u3 + 3.0 * u2, u3 - u2);
+                let segment = frames[2] - frames[1];
+                let start_tangent = segment / (frames[2] - frames[0]);
+                let end_tangent = segment / (frames[3] - frames[1]);
+                [
+                    -h10 * start_tangent,
+                    h00 - h11 * end_tangent,
+                    h01 + h10 * start_tangent,
+                    h11 * end_tangent,
+                ]
+            }
+        }
+    }
+}
+
+fn blend<T: Copy + Add<Output = T> + Mul<f64, Output = T>>(values: [T; 4], weights: [f64; 4]) -> T {
+    values[0] * weights[0]
+        + values[1] * weights[1]
+        + values[2] * weights[2]
+        + values[3] * weights[3]
+}
+
+// Camera animated along keyframes, for thin lens cameras with the y axis up
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>, // By frame
+    interpolation: Interpolation,
+    aspect_ratio: f64,
+    aperture: f64,
+    lens: LensSettings,
+}
+
+impl CameraPath {
+    pub fn new(
+        keyframes: Vec<Keyframe>,
+        interpolation: Interpolation,
+        aspect_ratio: f64,
+    ) -> anyhow::Result<Self> {
+        if keyframes.is_empty() {
+            bail!("camera path without keyframes");
+        }
+        if keyframes
+            .windows(2)
+            .any(|pair| pair[0].frame >= pair[1].frame)
+        {
+            bail!("camera path keyframes out of order");
+        }
+
+        Ok(Self {
+            keyframes,
+            interpolation,
+            aspect_ratio,
+            aperture: 0.0,
+            lens: LensSettings::default(),
+        })
+    }
+
+    // A full turn around look_at in the given number of frames, for asset reviews
+    pub fn turntable(
+        look_from: Point3,
+        look_at: Point3,
+        vertical_fov: f64,
+        aspect_ratio: f64,
+        frames: usize,
+    ) -> anyhow::Result<Self> {
+        if frames == 0 {
+            bail!("turntable without frames");
+        }
+        let axis = Vec3::new(0.0, 1.0, 0.0);
+        let focus_distance = (look_from - look_at).length();
+        let keyframes = (0..frames)
+            .map(|frame| {
+                let angle = 360.0 * frame as f64 / frames as f64;
+                let orbit = Transform::rotation(&axis, angle);
+                Keyframe {
+                    frame,
+                    look_from: look_at + orbit.vector(&(look_from - look_at)),
+                    look_at,
+                    vertical_fov,
+                    focus_distance,
+                }
+            })
+            .collect();
+
+        Self::new(keyframes, Interpolation::Linear, aspect_ratio)
+    }
+
+    pub fn with_aperture(self, aperture: f64) -> Self {
+        Self { aperture, ..self }
+    }
+
+    pub fn with_lens(self, lens: LensSettings) -> Self {
+        Self { lens, ..self }
+    }
+
+    pub fn frames(&self) -> RangeInclusive<usize> {
+        let first = self.keyframes[0].frame;
+        let last = self.keyframes[self.keyframes.len() - 1].frame;
+
+        first..=last
+    }
+
+    pub fn camera(&self, frame: usize) -> ThinLensCamera {
+        let keyframe = self.keyframe(frame);
+        let lens = &self.lens;
+        let camera = ThinLensCamera::new(
+            keyframe.look_from,
+            keyframe.look_at,
+            Vec3::new(0.0, 1.0, 0.0),
+            keyframe.vertical_fov,
+            self.aspect_ratio,
+            self.aperture,
+            keyframe.focus_distance,
+        )
+        .with_shift(lens.shift.0, lens.shift.1)
+        .with_aperture(lens.aperture.clone())
+        .with_cat_eye(lens.cat_eye)
+        .with_shutter(lens.shutter.0, lens.shutter.1)
+        .with_exposure(lens.exposure);
+
+        match lens.tilt {
+            Some((tilt, swing)) => camera.with_tilt(tilt, swing),
+            None => camera,
+        }
+    }
+
+    // Camera settings at frame, held before the first keyframe and after the last one
+    fn keyframe(&self, frame: usize) -> Keyframe {
+        let end = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.frame <= frame);
+        if end == 0 || end == self.keyframes.len() {
+            let keyframe = self.keyframes[end.saturating_sub(1)];
+            return Keyframe { frame, ..keyframe };
+        }
+
+        // Keyframes around the segment, repeated at the ends of the path
+        let start = end - 1;
+        let around = [
+            self.keyframes[start.saturating_sub(1)],
+            self.keyframes[start],
+            self.keyframes[end],
+            self.keyframes[(end + 1).min(self.keyframes.len() - 1)],
+        ];
+        let u = (frame - around[1].frame) as f64 / (around[2].frame - around[1].frame) as f64;
+        let weights = self
+            .interpolation
+            .weights(u, around.map(|keyframe| keyframe.frame as f64));
+
+        Keyframe {
+            frame,
+            look_from: blend(around.map(|keyframe| keyframe.look_from), weights),
+            look_at: blend(around.map(|keyframe| keyframe.look_at), weights),
+            vertical_fov: blend(around.map(|keyframe| keyframe.vertical_fov), weights),
+            focus_distance: blend(around.map(|keyframe| keyframe.focus_distance), weights),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(frame: usize, x: f64) -> Keyframe {
+        Keyframe {
+            frame,
+            look_from: Point3::new(x, 1.0, 0.0),
+            look_at: Point3::new(0.0, 0.0, 0.0),
+            vertical_fov: 40.0 + x,
+            focus_distance: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let keyframes = vec![keyframe(0, 0.0), keyframe(10, 1.0), keyframe(30, 5.0)];
+        assert!(CameraPath::new(vec![], Interpolation::Linear, 1.0).is_err());
+        assert!(CameraPath::new(
+            keyframes.iter().rev().copied().collect(),
+            Interpolation::Linear,
+            1.0
+        )
+        .is_err());
+
+        let linear = CameraPath::new(keyframes.clone(), Interpolation::Linear, 1.0).unwrap();
+        assert_eq!(0..=30, linear.frames());
+        assert_eq!(keyframe(20, 3.0), linear.keyframe(20));
+        assert_eq!(keyframe(35, 5.0), linear.keyframe(35));
+
+        // The spline goes through the keyframes
+        let spline = CameraPath::new(keyframes.clone(), Interpolation::CatmullRom, 1.0).unwrap();
+        for keyframe in &keyframes {
+            assert_eq!(*keyframe, spline.keyframe(keyframe.frame));
+        }
+
+        // Without a jump of speed at a keyframe, even with segments of different lengths: on both
+        // sides it's close to the one between its neighbors, 5 / 30 per frame
+        let x = |frame| spline.keyframe(frame).look_from.x();
+        assert!((x(10) - x(9) - 5.0 / 30.0).abs() < 0.02);
+        assert!((x(11) - x(10) - 5.0 / 30.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_lens() {
+        let lens = LensSettings {
+            shift: (0.0, 0.25),
+            shutter: (0.5, 0.75),
+            exposure: 2.0,
+            ..LensSettings::default()
+        };
+        let keyframes = vec![keyframe(0, 1.0), keyframe(10, 3.0)];
+        let path = CameraPath::new(keyframes, Interpolation::Linear, 1.0)
+            .unwrap()
+            .with_lens(lens);
+
+        // Every frame keeps the settings of the lens
+        for frame in [0, 5, 10] {
+            let camera = path.camera(frame);
+            assert_eq!(2.0, camera.exposure());
+
+            let keyframe = path.keyframe(frame);
+            let shifted = ThinLensCamera::new(
+                keyframe.look_from,
+                keyframe.look_at,
+                Vec3::new(0.0, 1.0, 0.0),
+                keyframe.vertical_fov,
+                1.0,
+                0.0,
+                keyframe.focus_distance,
+            )
+            .with_shift(0.0, 0.25);
+            let r = camera.get_ray(0.5, 0.5).unwrap();
+            let expected = shifted.get_ray(0.5, 0.5).unwrap();
+            assert!((r.direction() - expected.direction()).length() < 1e-12);
+            assert!((0.5..=0.75).contains(&r.time()), "{}", r.time());
+        }
+    }
+
+    #[test]
+    fn test_turntable() {
+        let look_from = Point3::new(0.0, 2.0, 5.0);
+        let look_at = Point3::new(0.0, 1.0, 0.0);
+        assert!(CameraPath::turntable(look_from, look_at, 30.0, 1.0, 0).is_err());
+        let turntable = CameraPath::turntable(look_from, look_at, 30.0, 1.0, 4).unwrap();
+        assert_eq!(0..=3, turntable.frames());
+
+        let quarter = turntable.keyframe(1).look_from;
+        assert!(
+            (quarter - Point3::new(5.0, 2.0, 0.0)).length() < 1e-9,
+            "{quarter:?}"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use crate::color::Color;
+use crate::fresnel::fr_complex;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::microfacet::TrowbridgeReitz;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Reflect, Unit};
+
+// Microfacet conductor
+//
+// GGX distribution with visible normal sampling, Smith height-correlated shadowing-masking and
+// the exact Fresnel equations for a complex index of refraction (eta + i*k).
+pub struct Conductor {
+    eta: Color,
+    k: Color,
+    distribution: TrowbridgeReitz,
+}
+
+impl Conductor {
+    pub fn new(eta: Color, k: Color, roughness: f64) -> Self {
+        let distribution = TrowbridgeReitz::new(roughness);
+        Self {
+            eta,
+            k,
+            distribution,
+        }
+    }
+
+    // RGB fits of the measured complex IOR of common metals.
+    pub fn gold(roughness: f64) -> Self {
+        let eta = Color::new(0.143, 0.374, 1.442);
+        let k = Color::new(3.983, 2.385, 1.603);
+        Self::new(eta, k, roughness)
+    }
+
+    pub fn copper(roughness: f64) -> Self {
+        let eta = Color::new(0.200, 0.924, 1.102);
+        let k = Color::new(3.912, 2.452, 2.142);
+        Self::new(eta, k, roughness)
+    }
+
+    pub fn aluminium(roughness: f64) -> Self {
+        let eta = Color::new(1.657, 0.880, 0.521);
+        let k = Color::new(9.224, 6.270, 4.837);
+        Self::new(eta, k, roughness)
+    }
+
+    pub fn silver(roughness: f64) -> Self {
+        let eta = Color::new(0.155, 0.117, 0.138);
+        let k = Color::new(4.828, 3.122, 2.147);
+        Self::new(eta, k, roughness)
+    }
+}
+
+impl Material for Conductor {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        let onb = Onb::new(hit_record.normal());
+        let wo = onb.to_local(&-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        if self.distribution.effectively_smooth() {
+            // Perfect specular reflection
+            let direction = r_in.direction().reflect(hit_record.normal());
+            let attenuation = fr_complex(wo.z(), &self.eta, &self.k);
+            let scattered = r_in.spawn(hit_record.p(), direction);
+
+            return Some((scattered, attenuation));
+        }
+
+        // Reflect wo on a microfacet normal sampled from the visible normals
+        let wm = self.distribution.sample_wm(&wo);
+        let wi = (-wo).reflect(wm);
+        if wi.z() <= 0.0 {
+            return None;
+        }
+
+        // With visible normal sampling the BRDF * cos / pdf simplifies to F * G / G1
+        let fresnel = fr_complex(wo.dot(wm), &self.eta, &self.k);
+        let masking = self.distribution.g(&wo, &wi) / self.distribution.g1(&wo);
+        let attenuation = masking * fresnel;
+
+        let scattered = r_in.spawn(hit_record.p(), onb.to_world(&wi));
+
+        Some((scattered, attenuation))
+    }
+}
@@ -0,0 +1,132 @@
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Unit, Vec3};
+
+// Capped cone standing on base, with its apex height units above it along +y.
+pub struct Cone {
+    base: Point3, // Center of the bottom cap
+    radius: f64,
+    height: f64,
+    material: Box<dyn Material>,
+}
+
+impl Cone {
+    pub fn new(base: Point3, radius: f64, height: f64, material: Box<dyn Material>) -> Self {
+        Self {
+            base,
+            radius,
+            height,
+            material,
+        }
+    }
+}
+
+impl Hittable for Cone {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let o = r.origin() - self.base;
+        let d = r.direction();
+        let mut closest: Option<(f64, Vec3)> = None;
+        let mut consider = |t: f64, outward_normal: Vec3| {
+            if t_range.contains(&t) && closest.is_none_or(|(closest_t, _)| t < closest_t) {
+                closest = Some((t, outward_normal));
+            }
+        };
+
+        // Side: x^2 + z^2 = k^2 * (height - y)^2, with k = radius / height and 0 <= y <= height
+        let k2 = (self.radius / self.height).powi(2);
+        let h = self.height - o.y();
+        let a = d.x().mul_add(d.x(), d.z() * d.z()) - k2 * d.y() * d.y();
+        let half_b = o.x().mul_add(d.x(), o.z() * d.z()) + k2 * h * d.y();
+        let c = o.x().mul_add(o.x(), o.z() * o.z()) - k2 * h * h;
+        let discriminant = half_b.mul_add(half_b, -a * c);
+        if a != 0.0 && discriminant >= 0.0 {
+            let sqrtd = discriminant.sqrt();
+            for t in [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a] {
+                let p = o + t * d;
+                if (0.0..=self.height).contains(&p.y()) {
+                    // Gradient of the implicit surface
+                    let normal = Vec3::new(p.x(), k2 * (self.height - p.y()), p.z());
+                    if normal.x() != 0.0 || normal.z() != 0.0 {
+                        consider(t, normal.unit());
+                    }
+                }
+            }
+        }
+
+        // Base cap
+        if d.y() != 0.0 {
+            let t = -o.y() / d.y();
+            let p = o + t * d;
+            if p.x().mul_add(p.x(), p.z() * p.z()) <= self.radius * self.radius {
+                consider(t, Vec3::new(0.0, -1.0, 0.0));
+            }
+        }
+
+        let (t, outward_normal) = closest?;
+        let p = r.at(t);
+
+        // UVs: u goes around the axis and v up to the apex, or from the center to the rim on the
+        // cap
+        let local = p - self.base;
+        let u = (local.z().atan2(local.x()) + PI) / (2.0 * PI);
+        let v = match outward_normal.y() == -1.0 {
+            true => local.x().hypot(local.z()) / self.radius,
+            false => local.y() / self.height,
+        };
+        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r).with_uv(u, v);
+
+        Some(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+    use crate::vec3::Length;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let cone = Cone::new(Point3::new(0.0, 0.0, 0.0), 1.0, 2.0, material);
+        let t_range = 0.001..f64::INFINITY;
+        let slope = Vec3::new(2.0, 1.0, 0.0).unit();
+
+        // Side, halfway up where the radius is 0.5
+        let r = Ray::new(Point3::new(5.0, 1.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        let hit_record = cone.hit(&r, &t_range).unwrap();
+        assert!((hit_record.t() - 4.5).abs() < 1e-12);
+        assert!((hit_record.outward_normal() - slope).length() < 1e-12);
+        assert!((hit_record.uv().1 - 0.5).abs() < 1e-12);
+
+        // From inside, where the radius is 0.75
+        let r = Ray::new(Point3::new(0.0, 0.5, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit_record = cone.hit(&r, &t_range).unwrap();
+        assert!((hit_record.t() - 0.75).abs() < 1e-12);
+        assert!(!hit_record.front_face());
+        assert!((hit_record.outward_normal() - slope).length() < 1e-12);
+
+        // Base cap, from below and from inside
+        let r = Ray::new(Point3::new(0.5, -3.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        let hit_record = cone.hit(&r, &t_range).unwrap();
+        assert_eq!(3.0, hit_record.t());
+        assert_eq!(Vec3::new(0.0, -1.0, 0.0), *hit_record.normal());
+        let r = Ray::new(Point3::new(0.2, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let hit_record = cone.hit(&r, &t_range).unwrap();
+        assert_eq!(1.0, hit_record.t());
+        assert!(!hit_record.front_face());
+
+        // Above the apex, where the other nappe of the double cone is, and past the base
+        let r = Ray::new(Point3::new(5.0, 2.5, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert!(cone.hit(&r, &t_range).is_none());
+        let r = Ray::new(Point3::new(1.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert!(cone.hit(&r, &t_range).is_none());
+    }
+}
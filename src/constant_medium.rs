@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+use crate::color::Color;
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::isotropic::Isotropic;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{Length, Vec3};
+
+// Volume of constant density, such as smoke or fog, filling a closed boundary
+//
+// A ray going through the volume scatters at an exponentially distributed distance: the
+// probability of scattering in a small distance dL is density * dL. If that distance is past the
+// boundary the ray goes through unaffected.
+pub struct ConstantMedium {
+    boundary: Box<dyn Hittable>,
+    neg_inv_density: f64,
+    phase_function: Box<dyn Material>,
+}
+
+impl ConstantMedium {
+    pub fn new(boundary: Box<dyn Hittable>, density: f64, albedo: Color) -> Self {
+        Self {
+            boundary,
+            neg_inv_density: -1.0 / density,
+            phase_function: Box::new(Isotropic::new(albedo)),
+        }
+    }
+}
+
+impl Hittable for ConstantMedium {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Entry and exit points of the whole line, the ray origin may be inside the volume
+        let entry = self.boundary.hit(r, &(f64::NEG_INFINITY..f64::INFINITY))?;
+        let exit = self.boundary.hit(r, &(entry.t() + 0.0001..f64::INFINITY))?;
+
+        let t_entry = entry.t().max(t_range.start).max(0.0);
+        let t_exit = exit.t().min(t_range.end);
+        if t_entry >= t_exit {
+            return None;
+        }
+
+        let ray_length = r.direction().length();
+        let distance_inside_boundary = (t_exit - t_entry) * ray_length;
+        let hit_distance = self.neg_inv_density * fastrand::f64().ln();
+        if hit_distance > distance_inside_boundary {
+            return None;
+        }
+
+        // The normal and the face are meaningless inside a volume
+        let t = t_entry + hit_distance / ray_length;
+        let p = r.at(t);
+        let normal = Vec3::new(1.0, 0.0, 0.0);
+
+        Some(HitRecord::new(p, normal, &*self.phase_function, t, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cuboid::Cuboid;
+    use crate::lambertian::Lambertian;
+    use crate::point3::Point3;
+
+    const SAMPLES: usize = 20000;
+
+    // Fraction of the rays going through the medium, and the range of their scattering t.
+    fn transmission(medium: &ConstantMedium, r: &Ray) -> (f64, Range<f64>) {
+        let hits: Vec<f64> = (0..SAMPLES)
+            .filter_map(|_| medium.hit(r, &(0.001..f64::INFINITY)))
+            .map(|hit_record| hit_record.t())
+            .collect();
+        let t_min = hits.iter().copied().fold(f64::INFINITY, f64::min);
+        let t_max = hits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        (1.0 - hits.len() as f64 / SAMPLES as f64, t_min..t_max)
+    }
+
+    #[test]
+    fn test_transmission() {
+        // Slab 2 units thick along x, of density 0.5
+        let boundary = Box::new(Cuboid::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 1.0),
+            Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5))),
+        ));
+        let medium = ConstantMedium::new(boundary, 0.5, Color::new(1.0, 1.0, 1.0));
+
+        // Through it, the light left is e^(-density * distance), whatever the ray length
+        let r = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(2.0, 0.0, 0.0));
+        let (passed, t) = transmission(&medium, &r);
+        assert!((passed - (-1_f64).exp()).abs() < 0.02, "{passed}");
+        assert!(t.start >= 0.5 && t.end <= 1.5, "{t:?}");
+
+        // From its middle, only half of it is left to go through
+        let r = Ray::new(Point3::new(1.0, 0.5, 0.5), Vec3::new(1.0, 0.0, 0.0));
+        let (passed, t) = transmission(&medium, &r);
+        assert!((passed - (-0.5_f64).exp()).abs() < 0.02, "{passed}");
+        assert!(t.start >= 0.0 && t.end <= 1.0, "{t:?}");
+
+        // Going away from it
+        let r = Ray::new(Point3::new(-1.0, 0.5, 0.5), Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(1.0, transmission(&medium, &r).0);
+    }
+}
@@ -0,0 +1,218 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::ray::Ray;
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl Operation {
+    fn inside(&self, inside_left: bool, inside_right: bool) -> bool {
+        match self {
+            Self::Union => inside_left || inside_right,
+            Self::Intersection => inside_left && inside_right,
+            Self::Difference => inside_left && !inside_right,
+        }
+    }
+}
+
+// Constructive solid geometry
+//
+// Combines two closed hittables, walking their hits along the ray in order. Each hit toggles
+// whether the ray is inside that child, and the ones that change whether it's inside the
+// combined solid are its surface. A ray starts inside a child if the first hit leaves it.
+pub struct Csg {
+    operation: Operation,
+    left: Box<dyn Hittable>,
+    right: Box<dyn Hittable>,
+}
+
+impl Csg {
+    // Distance to skip past a hit to find the next one
+    const EPSILON: f64 = 0.0001;
+
+    pub fn union(left: Box<dyn Hittable>, right: Box<dyn Hittable>) -> Self {
+        Self {
+            operation: Operation::Union,
+            left,
+            right,
+        }
+    }
+
+    pub fn intersection(left: Box<dyn Hittable>, right: Box<dyn Hittable>) -> Self {
+        Self {
+            operation: Operation::Intersection,
+            left,
+            right,
+        }
+    }
+
+    // Left with right carved out of it
+    pub fn difference(left: Box<dyn Hittable>, right: Box<dyn Hittable>) -> Self {
+        Self {
+            operation: Operation::Difference,
+            left,
+            right,
+        }
+    }
+}
+
+fn next_hit<'a>(object: &'a dyn Hittable, r: &Ray, t: f64) -> Option<HitRecord<'a>> {
+    object.hit(r, &(t..f64::INFINITY))
+}
+
+impl Hittable for Csg {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let mut hit_left = next_hit(&*self.left, r, t_range.start);
+        let mut hit_right = next_hit(&*self.right, r, t_range.start);
+        let mut inside_left = hit_left.as_ref().is_some_and(|h| !h.front_face());
+        let mut inside_right = hit_right.as_ref().is_some_and(|h| !h.front_face());
+
+        loop {
+            let t_left = hit_left.as_ref().map_or(f64::INFINITY, |h| h.t());
+            let t_right = hit_right.as_ref().map_or(f64::INFINITY, |h| h.t());
+            let t = t_left.min(t_right);
+            if t >= t_range.end {
+                return None;
+            }
+
+            let inside = self.operation.inside(inside_left, inside_right);
+            let from_left = t_left <= t_right;
+            match from_left {
+                true => inside_left = !inside_left,
+                false => inside_right = !inside_right,
+            }
+
+            if inside != self.operation.inside(inside_left, inside_right) {
+                let hit_record = match from_left {
+                    true => hit_left?,
+                    false => hit_right?,
+                };
+
+                // The surface carved by the right object faces the other way
+                if matches!(self.operation, Operation::Difference) && !from_left {
+                    let (u, v) = hit_record.uv();
+                    let outward_normal = -hit_record.outward_normal();
+                    let material = hit_record.material();
+                    let p = hit_record.p();
+                    return Some(HitRecord::new(p, outward_normal, material, t, r).with_uv(u, v));
+                }
+
+                return Some(hit_record);
+            }
+
+            match from_left {
+                true => hit_left = next_hit(&*self.left, r, t + Self::EPSILON),
+                false => hit_right = next_hit(&*self.right, r, t + Self::EPSILON),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+    use crate::point3::Point3;
+    use crate::sphere::Sphere;
+    use crate::vec3::{Length, Vec3};
+
+    // Unit spheres centered at x = -0.5 and x = 0.5, overlapping between them
+    fn csg(operation: fn(Box<dyn Hittable>, Box<dyn Hittable>) -> Csg) -> Csg {
+        let sphere = |x: f64| {
+            let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+            Box::new(Sphere::new(Point3::new(x, 0.0, 0.0), 1.0, material))
+        };
+
+        operation(sphere(-0.5), sphere(0.5))
+    }
+
+    // t, outward normal and front face of the hit of the ray from origin along direction
+    fn hit(csg: &Csg, origin: Point3, direction: Vec3) -> Option<(f64, Vec3, bool)> {
+        let hit_record = csg.hit(&Ray::new(origin, direction), &(0.001..f64::INFINITY))?;
+
+        Some((
+            hit_record.t(),
+            hit_record.outward_normal(),
+            hit_record.front_face(),
+        ))
+    }
+
+    fn assert_hit(hit: Option<(f64, Vec3, bool)>, t: f64, normal: Vec3, front_face: bool) {
+        let (hit_t, hit_normal, hit_front_face) = hit.unwrap();
+        assert!((hit_t - t).abs() < 1e-9, "{hit_t}");
+        assert!((hit_normal - normal).length() < 1e-9, "{hit_normal:?}");
+        assert_eq!(front_face, hit_front_face);
+    }
+
+    #[test]
+    fn test_union() {
+        let (left, right) = (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let union = csg(Csg::union);
+
+        // From outside, into the left sphere
+        let outside = hit(&union, Point3::new(-5.0, 0.0, 0.0), right);
+        assert_hit(outside, 3.5, left, true);
+
+        // From inside the left sphere, out through the right one only
+        let inside = hit(&union, Point3::new(-1.0, 0.0, 0.0), right);
+        assert_hit(inside, 2.5, right, false);
+
+        // Grazing both spheres, and above them
+        let grazing = hit(&union, Point3::new(-5.0, 0.999, 0.0), right);
+        assert!(grazing.is_some_and(|(t, _, _)| t < 4.5));
+        assert_eq!(None, hit(&union, Point3::new(-5.0, 2.0, 0.0), right));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let (left, right) = (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let intersection = csg(Csg::intersection);
+
+        // From outside, into the lens shaped overlap through the right sphere
+        let outside = hit(&intersection, Point3::new(-5.0, 0.0, 0.0), right);
+        assert_hit(outside, 4.5, left, true);
+
+        // From inside the left sphere only, and from inside both
+        let inside_left = hit(&intersection, Point3::new(-1.0, 0.0, 0.0), right);
+        assert_hit(inside_left, 0.5, left, true);
+        let inside_both = hit(&intersection, Point3::new(0.0, 0.0, 0.0), right);
+        assert_hit(inside_both, 0.5, right, false);
+
+        // Grazing both spheres away from the overlap, and through the right one only
+        let grazing = hit(&intersection, Point3::new(-5.0, 0.999, 0.0), right);
+        assert_eq!(None, grazing);
+        let down = Vec3::new(0.0, -1.0, 0.0);
+        assert_eq!(None, hit(&intersection, Point3::new(1.2, 5.0, 0.0), down));
+    }
+
+    #[test]
+    fn test_difference() {
+        let (left, right) = (Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let difference = csg(Csg::difference);
+
+        // From outside, into the left sphere
+        let outside = hit(&difference, Point3::new(-5.0, 0.0, 0.0), right);
+        assert_hit(outside, 3.5, left, true);
+
+        // From inside, out through the carved surface, which faces into the right sphere
+        let inside = hit(&difference, Point3::new(-1.0, 0.0, 0.0), right);
+        assert_hit(inside, 0.5, right, false);
+
+        // From the carved out part, back into the solid through the same surface
+        let carved = hit(&difference, Point3::new(0.0, 0.0, 0.0), left);
+        assert_hit(carved, 0.5, right, true);
+
+        // From the right, all the way through the right sphere to the carved surface
+        let from_right = hit(&difference, Point3::new(5.0, 0.0, 0.0), left);
+        assert_hit(from_right, 5.5, right, true);
+        let down = Vec3::new(0.0, -1.0, 0.0);
+        assert_eq!(None, hit(&difference, Point3::new(1.2, 5.0, 0.0), down));
+    }
+}
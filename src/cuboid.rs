@@ -0,0 +1,134 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+// Axis aligned box between the corners min and max.
+pub struct Cuboid {
+    min: Point3,
+    max: Point3,
+    material: Box<dyn Material>,
+}
+
+impl Cuboid {
+    pub fn new(a: Point3, b: Point3, material: Box<dyn Material>) -> Self {
+        let min = Point3::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z()));
+        let max = Point3::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z()));
+        Self { min, max, material }
+    }
+}
+
+fn axis(v: &Vec3, axis: usize) -> f64 {
+    match axis {
+        0 => v.x(),
+        1 => v.y(),
+        _ => v.z(),
+    }
+}
+
+fn unit_axis(axis: usize, sign: f64) -> Vec3 {
+    match axis {
+        0 => Vec3::new(sign, 0.0, 0.0),
+        1 => Vec3::new(0.0, sign, 0.0),
+        _ => Vec3::new(0.0, 0.0, sign),
+    }
+}
+
+impl Hittable for Cuboid {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Slab method: the ray is inside the box when it's between the two planes of every axis.
+        // Keep track of the axis of the last entry and of the first exit for the normals.
+        let mut t_enter = (f64::NEG_INFINITY, 0);
+        let mut t_exit = (f64::INFINITY, 0);
+        for i in 0..3 {
+            let origin = axis(r.origin(), i);
+            if axis(r.direction(), i) == 0.0 {
+                // Parallel to the slab, 0 * inf would be NaN on its planes
+                if origin < axis(&self.min, i) || origin > axis(&self.max, i) {
+                    return None;
+                }
+                continue;
+            }
+            let inverse_direction = 1.0 / axis(r.direction(), i);
+            let mut t0 = (axis(&self.min, i) - origin) * inverse_direction;
+            let mut t1 = (axis(&self.max, i) - origin) * inverse_direction;
+            if inverse_direction < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            if t0 > t_enter.0 {
+                t_enter = (t0, i);
+            }
+            if t1 < t_exit.0 {
+                t_exit = (t1, i);
+            }
+            if t_exit.0 <= t_enter.0 {
+                return None;
+            }
+        }
+
+        // Outward normals: the slab is entered against the direction, and exited along it
+        let (t, i) = match (t_range.contains(&t_enter.0), t_range.contains(&t_exit.0)) {
+            (true, _) => t_enter,
+            (false, true) => t_exit,
+            (false, false) => return None,
+        };
+        let sign = match t == t_enter.0 {
+            true => -axis(r.direction(), i).signum(),
+            false => axis(r.direction(), i).signum(),
+        };
+        let outward_normal = unit_axis(i, sign);
+
+        // UVs are the coordinates in the face, from 0 to 1
+        let p = r.at(t);
+        let relative = |j: usize| {
+            let j = (i + j) % 3;
+            (axis(&p, j) - axis(&self.min, j)) / (axis(&self.max, j) - axis(&self.min, j))
+        };
+        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r)
+            .with_uv(relative(1), relative(2));
+
+        Some(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let cuboid = Cuboid::new(
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(-1.0, -1.0, -1.0),
+            material,
+        );
+        let t_range = 0.001..f64::INFINITY;
+
+        // Straight into the front face
+        let r = Ray::new(Point3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        let hit_record = cuboid.hit(&r, &t_range).unwrap();
+        assert_eq!(4.0, hit_record.t());
+        assert_eq!(Vec3::new(0.0, 0.0, 1.0), *hit_record.normal());
+        assert_eq!((0.5, 0.5), hit_record.uv());
+
+        // From inside, out of the right face
+        let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let hit_record = cuboid.hit(&r, &t_range).unwrap();
+        assert_eq!(1.0, hit_record.t());
+        assert!(!hit_record.front_face());
+        assert_eq!(Vec3::new(1.0, 0.0, 0.0), hit_record.outward_normal());
+
+        // Along the plane of the top face, and above it
+        let r = Ray::new(Point3::new(0.0, 1.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(4.0, cuboid.hit(&r, &t_range).unwrap().t());
+        let r = Ray::new(Point3::new(0.0, 1.5, 5.0), Vec3::new(0.0, 0.0, -1.0));
+        assert!(cuboid.hit(&r, &t_range).is_none());
+    }
+}
@@ -0,0 +1,128 @@
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::Vec3;
+
+// Capped cylinder standing on base, with its axis along +y.
+pub struct Cylinder {
+    base: Point3, // Center of the bottom cap
+    radius: f64,
+    height: f64,
+    material: Box<dyn Material>,
+}
+
+impl Cylinder {
+    pub fn new(base: Point3, radius: f64, height: f64, material: Box<dyn Material>) -> Self {
+        Self {
+            base,
+            radius,
+            height,
+            material,
+        }
+    }
+}
+
+impl Hittable for Cylinder {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let o = r.origin() - self.base;
+        let d = r.direction();
+        let mut closest: Option<(f64, Vec3)> = None;
+        let mut consider = |t: f64, outward_normal: Vec3| {
+            if t_range.contains(&t) && closest.is_none_or(|(closest_t, _)| t < closest_t) {
+                closest = Some((t, outward_normal));
+            }
+        };
+
+        // Side: (Ox + t*Dx)^2 + (Oz + t*Dz)^2 = radius^2, with 0 <= y <= height
+        let a = d.x().mul_add(d.x(), d.z() * d.z());
+        let half_b = o.x().mul_add(d.x(), o.z() * d.z());
+        let c = o.x().mul_add(o.x(), o.z() * o.z()) - self.radius * self.radius;
+        let discriminant = half_b.mul_add(half_b, -a * c);
+        if a != 0.0 && discriminant >= 0.0 {
+            let sqrtd = discriminant.sqrt();
+            for t in [(-half_b - sqrtd) / a, (-half_b + sqrtd) / a] {
+                let y = o.y() + t * d.y();
+                if (0.0..=self.height).contains(&y) {
+                    let p = o + t * d;
+                    consider(t, Vec3::new(p.x(), 0.0, p.z()) / self.radius);
+                }
+            }
+        }
+
+        // Caps
+        if d.y() != 0.0 {
+            for (y, normal) in [(0.0, -1.0), (self.height, 1.0)] {
+                let t = (y - o.y()) / d.y();
+                let p = o + t * d;
+                if p.x().mul_add(p.x(), p.z() * p.z()) <= self.radius * self.radius {
+                    consider(t, Vec3::new(0.0, normal, 0.0));
+                }
+            }
+        }
+
+        let (t, outward_normal) = closest?;
+        let p = r.at(t);
+
+        // UVs: u goes around the axis and v along it, or from the center to the rim on the caps
+        let local = p - self.base;
+        let u = (local.z().atan2(local.x()) + PI) / (2.0 * PI);
+        let v = match outward_normal.y() == 0.0 {
+            true => local.y() / self.height,
+            false => local.x().hypot(local.z()) / self.radius,
+        };
+        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r).with_uv(u, v);
+
+        Some(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let cylinder = Cylinder::new(Point3::new(0.0, 0.0, 0.0), 1.0, 2.0, material);
+        let t_range = 0.001..f64::INFINITY;
+        let hit = |origin: Point3, direction: Vec3| {
+            let hit_record = cylinder.hit(&Ray::new(origin, direction), &t_range)?;
+            Some((
+                hit_record.t(),
+                hit_record.outward_normal(),
+                hit_record.front_face(),
+            ))
+        };
+
+        // Side, from outside and from inside
+        let side = hit(Point3::new(5.0, 1.0, 0.0), Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(Some((4.0, Vec3::new(1.0, 0.0, 0.0), true)), side);
+        let side = hit(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0));
+        assert_eq!(Some((1.0, Vec3::new(0.0, 0.0, 1.0), false)), side);
+
+        // Caps, from above, below and inside
+        let top = hit(Point3::new(0.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        assert_eq!(Some((3.0, Vec3::new(0.0, 1.0, 0.0), true)), top);
+        let bottom = hit(Point3::new(0.0, -2.0, 0.5), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(Some((2.0, Vec3::new(0.0, -1.0, 0.0), true)), bottom);
+        let top = hit(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(Some((1.0, Vec3::new(0.0, 1.0, 0.0), false)), top);
+
+        // Above it, and down past its side
+        assert_eq!(
+            None,
+            hit(Point3::new(5.0, 3.0, 0.0), Vec3::new(-1.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            None,
+            hit(Point3::new(1.5, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0))
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::io::Read;
+
+use anyhow::{bail, Context};
+
+use crate::perlin::Perlin;
+use crate::point3::Point3;
+
+// Density of a heterogeneous medium, in its object space
+pub trait DensityField: Send + Sync {
+    fn density(&self, p: &Point3) -> f64;
+
+    // Upper bound of the density everywhere, the majorant of delta tracking.
+    fn max_density(&self) -> f64;
+}
+
+// Voxel grid of densities spanning the unit cube [0, 1]^3
+//
+// Densities are stored at the center of the voxels and trilinearly interpolated in between.
+pub struct DensityGrid {
+    dimensions: [usize; 3],
+    values: Vec<f32>, // x first, then y, then z
+    max_density: f64,
+}
+
+impl DensityGrid {
+    const MAGIC: &'static [u8; 4] = b"DGRD";
+
+    pub fn new(dimensions: [usize; 3], values: Vec<f32>) -> anyhow::Result<Self> {
+        let [nx, ny, nz] = dimensions;
+        if nx == 0 || ny == 0 || nz == 0 {
+            bail!("empty density grid: {nx}x{ny}x{nz}");
+        }
+        let Some(count) = nx.checked_mul(ny).and_then(|nxy| nxy.checked_mul(nz)) else {
+            bail!("density grid too large: {nx}x{ny}x{nz}");
+        };
+        if values.len() != count {
+            bail!("{nx}x{ny}x{nz} density grid with {} values", values.len());
+        }
+        if values
+            .iter()
+            .any(|value| !(value.is_finite() && *value >= 0.0))
+        {
+            bail!("density grid with negative or non finite values");
+        }
+
+        let max_density = values.iter().fold(0.0_f32, |max, value| max.max(*value)) as f64;
+
+        Ok(Self {
+            dimensions,
+            values,
+            max_density,
+        })
+    }
+
+    // Raw binary grid, all little endian:
+    //   - the magic bytes "DGRD"
+    //   - the dimensions nx, ny and nz as u32
+    //   - nx * ny * nz densities as f32, x first, then y, then z
+    pub fn read(mut reader: impl Read) -> anyhow::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic).context("reading magic")?;
+        if &magic != Self::MAGIC {
+            bail!("not a density grid, bad magic {magic:?}");
+        }
+
+        let mut read_u32 = || -> anyhow::Result<usize> {
+            let mut bytes = [0; 4];
+            reader
+                .read_exact(&mut bytes)
+                .context("reading dimensions")?;
+            Ok(u32::from_le_bytes(bytes) as usize)
+        };
+        let dimensions = [read_u32()?, read_u32()?, read_u32()?];
+
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .context("reading densities")?;
+        let values = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        Self::new(dimensions, values)
+    }
+
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path).with_context(|| format!("opening {path}"))?;
+        Self::read(std::io::BufReader::new(file)).with_context(|| format!("loading {path}"))
+    }
+
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f64 {
+        let [nx, ny, _] = self.dimensions;
+        self.values[x + nx * (y + ny * z)] as f64
+    }
+}
+
+impl DensityField for DensityGrid {
+    fn density(&self, p: &Point3) -> f64 {
+        let coordinates = [p.x(), p.y(), p.z()];
+        if coordinates.iter().any(|c| !(0.0..=1.0).contains(c)) {
+            return 0.0;
+        }
+
+        // Voxel below p and the interpolation weight towards the next one, on each axis
+        let mut cells = [(0, 0, 0.0); 3];
+        for (cell, (c, n)) in cells
+            .iter_mut()
+            .zip(coordinates.into_iter().zip(self.dimensions))
+        {
+            let x = (c * n as f64 - 0.5).clamp(0.0, (n - 1) as f64);
+            let i = (x as usize).min(n - 1);
+            *cell = (i, (i + 1).min(n - 1), x - i as f64);
+        }
+        let [(x0, x1, tx), (y0, y1, ty), (z0, z1, tz)] = cells;
+
+        let lerp = |a: f64, b: f64, t: f64| a + t * (b - a);
+        let plane = |z| {
+            let bottom = lerp(self.voxel(x0, y0, z), self.voxel(x1, y0, z), tx);
+            let top = lerp(self.voxel(x0, y1, z), self.voxel(x1, y1, z), tx);
+            lerp(bottom, top, ty)
+        };
+
+        lerp(plane(z0), plane(z1), tz)
+    }
+
+    fn max_density(&self) -> f64 {
+        self.max_density
+    }
+}
+
+// Procedural density from Perlin turbulence, for clouds
+pub struct NoiseDensity {
+    noise: Perlin,
+    density: f64,   // Density at full turbulence
+    frequency: f64, // Features per object space unit
+}
+
+impl NoiseDensity {
+    const OCTAVES: usize = 6;
+
+    pub fn new(density: f64, frequency: f64) -> Self {
+        Self {
+            noise: Perlin::new(),
+            density,
+            frequency,
+        }
+    }
+}
+
+impl DensityField for NoiseDensity {
+    fn density(&self, p: &Point3) -> f64 {
+        let turbulence = self.noise.turbulence(&(self.frequency * p), Self::OCTAVES);
+        self.density * turbulence.min(1.0)
+    }
+
+    fn max_density(&self) -> f64 {
+        self.density
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_bytes(dimensions: [u32; 3], values: &[f32]) -> Vec<u8> {
+        let mut bytes = DensityGrid::MAGIC.to_vec();
+        for n in dimensions {
+            bytes.extend(n.to_le_bytes());
+        }
+        for value in values {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_read() {
+        let bytes = grid_bytes([2, 1, 1], &[1.0, 3.0]);
+        let grid = DensityGrid::read(bytes.as_slice()).unwrap();
+
+        // Voxel centers, and halfway between them
+        assert_eq!(1.0, grid.density(&Point3::new(0.25, 0.5, 0.5)));
+        assert_eq!(3.0, grid.density(&Point3::new(0.75, 0.5, 0.5)));
+        assert_eq!(2.0, grid.density(&Point3::new(0.5, 0.5, 0.5)));
+        // Clamped at the border, and empty outside
+        assert_eq!(1.0, grid.density(&Point3::new(0.0, 0.0, 1.0)));
+        assert_eq!(0.0, grid.density(&Point3::new(1.5, 0.5, 0.5)));
+        assert_eq!(3.0, grid.max_density());
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(DensityGrid::read(&b"GRID"[..]).is_err());
+        let truncated = grid_bytes([2, 2, 2], &[1.0; 7]);
+        assert!(DensityGrid::read(truncated.as_slice()).is_err());
+        let negative = grid_bytes([1, 1, 1], &[-1.0]);
+        assert!(DensityGrid::read(negative.as_slice()).is_err());
+        let huge = grid_bytes([u32::MAX, u32::MAX, u32::MAX], &[1.0]);
+        assert!(DensityGrid::read(huge.as_slice()).is_err());
+    }
+}
@@ -1,18 +1,90 @@
-use crate::color::{Color, WHITE};
+use crate::color::{Color, BLACK, WHITE};
 use crate::hit_record::HitRecord;
 use crate::material::Material;
 use crate::ray::Ray;
-use crate::vec3::{refract, Dot, Reflect, Unit};
+use crate::vec3::{refract, Dot, Length, Reflect, Unit};
+
+// Index of refraction as a function of the wavelength, with λ in micrometers.
+#[derive(Clone)]
+pub enum Dispersion {
+    // n = a + b / λ^2
+    Cauchy { a: f64, b: f64 },
+    // n^2 = 1 + Σ b_i λ^2 / (λ^2 - c_i)
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+impl Dispersion {
+    // Borosilicate crown glass
+    pub const BK7: Self = Self::Sellmeier {
+        b: [1.03961212, 0.231792344, 1.01046945],
+        c: [0.00600069867, 0.0200179144, 103.560653],
+    };
+    // Dense flint glass, the classic prism
+    pub const SF11: Self = Self::Sellmeier {
+        b: [1.73759695, 0.313747346, 1.89878101],
+        c: [0.013188707, 0.0623068142, 155.23629],
+    };
+    pub const DIAMOND: Self = Self::Sellmeier {
+        b: [4.3356, 0.3306, 0.0],
+        c: [0.011236, 0.030625, 0.0],
+    };
+
+    // Fraunhofer d line, the reference wavelength for the index of refraction of glasses.
+    const D_LINE: f64 = 587.56;
+
+    pub fn ir(&self, lambda_nm: f64) -> f64 {
+        let lambda = lambda_nm / 1000.0;
+        let lambda2 = lambda * lambda;
+        match self {
+            Self::Cauchy { a, b } => a + b / lambda2,
+            Self::Sellmeier { b, c } => {
+                let n2 = b
+                    .iter()
+                    .zip(c.iter())
+                    .map(|(b, c)| b * lambda2 / (lambda2 - c))
+                    .sum::<f64>()
+                    + 1.0;
+                n2.sqrt()
+            }
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Dielectric {
-    ir: f64, // Index of Refraction
+    ir: f64,                        // Index of Refraction
+    dispersion: Option<Dispersion>, // Index of Refraction per wavelength, in spectral mode
+    absorption: Color,              // Absorption coefficient of the medium, per unit of distance
 }
 
 impl Dielectric {
     const ATTENUATION: Color = WHITE;
     pub fn new(ir: f64) -> Self {
-        Self { ir }
+        Self {
+            ir,
+            dispersion: None,
+            absorption: BLACK,
+        }
+    }
+
+    // Colored glass: light that travels `distance` units inside the medium keeps `tint` of its
+    // energy.
+    pub fn tinted(ir: f64, tint: Color, distance: f64) -> anyhow::Result<Self> {
+        let absorption = absorption_from_tint(&tint, distance)?;
+        Ok(Self {
+            absorption,
+            ..Self::new(ir)
+        })
+    }
+
+    // Glass that splits the light into a rainbow when rendered in spectral mode. The RGB renderer
+    // uses the index of refraction at the d line.
+    pub fn dispersive(dispersion: Dispersion) -> Self {
+        Self {
+            ir: dispersion.ir(Dispersion::D_LINE),
+            dispersion: Some(dispersion),
+            ..Self::new(1.0)
+        }
     }
 
     fn reflectance(cos_theta: f64, ref_idx: f64) -> f64 {
@@ -26,9 +98,13 @@ impl Dielectric {
 
 impl Material for Dielectric {
     fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        let ir = match (&self.dispersion, r_in.wavelengths()) {
+            (Some(dispersion), Some(wavelengths)) => dispersion.ir(wavelengths.hero()),
+            _ => self.ir,
+        };
         let refraction_ratio = match hit_record.front_face() {
-            true => 1.0 / self.ir,
-            false => self.ir,
+            true => 1.0 / ir,
+            false => ir,
         };
 
         let unit_direction = r_in.direction().unit();
@@ -41,8 +117,62 @@ impl Material for Dielectric {
             false => refract(unit_direction, hit_record.normal(), refraction_ratio),
         };
 
-        let scattered = Ray::new(hit_record.p(), direction);
+        let mut scattered = r_in.spawn(hit_record.p(), direction);
+        if self.dispersion.is_some() {
+            // Each wavelength would follow its own direction, keep only the hero one.
+            scattered.terminate_secondary_wavelengths();
+        }
+        let attenuation = Self::ATTENUATION * transmittance(&self.absorption, r_in, hit_record);
+
+        Some((scattered, attenuation))
+    }
+}
+
+// Absorption coefficient that leaves `tint` of the light after traveling `distance` units.
+pub fn absorption_from_tint(tint: &Color, distance: f64) -> anyhow::Result<Color> {
+    if distance.is_nan() || distance <= 0.0 {
+        anyhow::bail!("tint distance must be positive, got {distance}");
+    }
+
+    let absorption = |channel: f64| -channel.max(f64::MIN_POSITIVE).ln() / distance;
+    Ok(Color::new(
+        absorption(tint.x()),
+        absorption(tint.y()),
+        absorption(tint.z()),
+    ))
+}
+
+// Beer-Lambert law
+//
+// A ray that hits the back face of the interface has been traveling inside the medium since its
+// previous bounce, so it's attenuated exponentially with the distance traveled.
+pub fn transmittance(absorption: &Color, r_in: &Ray, hit_record: &HitRecord) -> Color {
+    if hit_record.front_face() || *absorption == BLACK {
+        return WHITE;
+    }
+
+    let distance = hit_record.t() * r_in.direction().length();
+    let transmittance = |channel: f64| (-channel * distance).exp();
+    Color::new(
+        transmittance(absorption.x()),
+        transmittance(absorption.y()),
+        transmittance(absorption.z()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absorption_from_tint() {
+        let absorption = absorption_from_tint(&Color::new(1.0, 0.5, 0.25), 2.0).unwrap();
+        assert_eq!(0.0, absorption.x());
+        assert!((absorption.y() - 2_f64.ln() / 2.0).abs() < 1e-12);
+        assert!((absorption.z() - 2_f64.ln()).abs() < 1e-12);
 
-        Some((scattered, Self::ATTENUATION))
+        for distance in [0.0, -1.0, f64::NAN] {
+            assert!(absorption_from_tint(&WHITE, distance).is_err());
+        }
     }
 }
@@ -0,0 +1,28 @@
+use crate::color::Color;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::ray::Ray;
+
+// Area light: emits on its front face and doesn't scatter.
+pub struct DiffuseLight {
+    emit: Color,
+}
+
+impl DiffuseLight {
+    pub fn new(emit: Color) -> Self {
+        Self { emit }
+    }
+}
+
+impl Material for DiffuseLight {
+    fn scatter(&self, _r_in: &Ray, _hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        None
+    }
+
+    fn emitted(&self, hit_record: &HitRecord) -> Color {
+        match hit_record.front_face() {
+            true => self.emit,
+            false => Color::default(),
+        }
+    }
+}
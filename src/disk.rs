@@ -0,0 +1,142 @@
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Length, LengthSquared, Unit, Vec3};
+
+// Disk centered in center, facing normal.
+pub struct Disk {
+    center: Point3,
+    normal: Vec3,
+    radius: f64,
+    onb: Onb, // Tangent frame for the UVs and the sampling
+    material: Box<dyn Material>,
+}
+
+impl Disk {
+    pub fn new(center: Point3, normal: Vec3, radius: f64, material: Box<dyn Material>) -> Self {
+        let normal = normal.unit();
+        let onb = Onb::new(&normal);
+        Self {
+            center,
+            normal,
+            radius,
+            onb,
+            material,
+        }
+    }
+}
+
+impl Hittable for Disk {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let denominator = r.direction().dot(self.normal);
+        if denominator.abs() < 1e-8 {
+            // Parallel to the disk
+            return None;
+        }
+
+        let t = (self.center - r.origin()).dot(self.normal) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let p = r.at(t);
+        let local = self.onb.to_local(&(p - self.center));
+        let distance_squared = local.x() * local.x() + local.y() * local.y();
+        if distance_squared > self.radius * self.radius {
+            return None;
+        }
+
+        // Polar UVs: u goes around the disk, v from the center to the rim
+        let u = (local.y().atan2(local.x()) + PI) / (2.0 * PI);
+        let v = distance_squared.sqrt() / self.radius;
+        let hit_record = HitRecord::new(p, self.normal, &*self.material, t, r).with_uv(u, v);
+
+        Some(hit_record)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        let r = Ray::new(*origin, *direction);
+        let Some(hit_record) = self.hit(&r, &(0.001..f64::INFINITY)) else {
+            return 0.0;
+        };
+
+        // Convert the uniform area density to solid angle
+        let area = PI * self.radius * self.radius;
+        let distance_squared = hit_record.t() * hit_record.t() * direction.length_squared();
+        let cosine = (direction.dot(self.normal) / direction.length()).abs();
+
+        distance_squared / (cosine * area)
+    }
+
+    fn random(&self, origin: &Point3) -> Option<Vec3> {
+        // Uniform point on the disk
+        let r = self.radius * fastrand::f64().sqrt();
+        let phi = 2.0 * PI * fastrand::f64();
+        let local = Vec3::new(r * phi.cos(), r * phi.sin(), 0.0);
+
+        Some(self.center + self.onb.to_world(&local) - origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::hittable::tests::check_sampling;
+    use crate::lambertian::Lambertian;
+
+    // Unit disk at z = -1, facing the origin
+    fn disk() -> Disk {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        Disk::new(
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, 1.0),
+            1.0,
+            material,
+        )
+    }
+
+    #[test]
+    fn test_hit() {
+        let disk = disk();
+        let origin = Point3::new(0.0, 0.0, 0.0);
+        let t_range = 0.001..f64::INFINITY;
+
+        let r = Ray::new(origin, Vec3::new(0.0, 0.5, -1.0));
+        let hit_record = disk.hit(&r, &t_range).unwrap();
+        assert!((hit_record.t() - 1.0).abs() < 1e-12);
+        assert!((hit_record.uv().1 - 0.5).abs() < 1e-12);
+        assert_eq!(Vec3::new(0.0, 0.0, 1.0), *hit_record.normal());
+
+        // From behind, the normal faces the ray
+        let r = Ray::new(Point3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 0.0, 1.0));
+        let hit_record = disk.hit(&r, &t_range).unwrap();
+        assert!(!hit_record.front_face());
+        assert_eq!(Vec3::new(0.0, 0.0, -1.0), *hit_record.normal());
+
+        // Past the rim, and parallel to it
+        assert!(disk
+            .hit(&Ray::new(origin, Vec3::new(0.8, 0.8, -1.0)), &t_range)
+            .is_none());
+        assert!(disk
+            .hit(&Ray::new(origin, Vec3::new(1.0, 0.0, 0.0)), &t_range)
+            .is_none());
+    }
+
+    #[test]
+    fn test_sampling() {
+        let disk = disk();
+        check_sampling(&disk, &Point3::new(0.0, 0.0, 0.0));
+        check_sampling(&disk, &Point3::new(0.5, 0.5, -3.0));
+
+        // Area density converted to solid angle, straight in front of its center
+        let pdf = disk.pdf_value(&Point3::new(0.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        assert!((pdf - 1.0 / PI).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,91 @@
+use std::f64::consts::PI;
+
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{MulAdd, Vec3};
+
+// 360 degrees panorama, for VR and environment maps
+//
+// The film maps longitude to s and latitude to t, centered on look_at. The whole sphere fits in
+// a 2:1 film.
+pub struct EquirectangularCamera {
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    shutter: Shutter,
+}
+
+impl EquirectangularCamera {
+    pub fn new(look_from: Point3, look_at: Point3, up: Vec3) -> Self {
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+
+        Self {
+            origin: look_from,
+            u,
+            v,
+            w,
+            shutter: Shutter::default(),
+        }
+    }
+}
+
+// Direction of longitude phi, growing to the right, and latitude theta, growing up, around -w.
+pub fn spherical_direction(u: &Vec3, v: &Vec3, w: &Vec3, phi: f64, theta: f64) -> Vec3 {
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let (sin_theta, cos_theta) = theta.sin_cos();
+
+    u.mul_add(
+        cos_theta * sin_phi,
+        v.mul_add(sin_theta, -cos_theta * cos_phi * w),
+    )
+}
+
+impl Camera for EquirectangularCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        let phi = (s - 0.5) * 2.0 * PI;
+        let theta = (t - 0.5) * PI;
+        let direction = spherical_direction(&self.u, &self.v, &self.w, phi, theta);
+
+        Some(Ray::new(self.origin, direction).with_time(self.shutter.sample()))
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3::{Length, Unit};
+
+    #[test]
+    fn test_get_ray() {
+        let camera = EquirectangularCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+        let direction = |s: f64, t: f64| camera.get_ray(s, t).unwrap().direction().unit();
+        let assert_close = |a: Vec3, b: Vec3| assert!((a - b).length() < 1e-9, "{a:?} {b:?}");
+
+        // Center on look_at, a quarter turn to the right
+        assert_close(Vec3::new(0.0, 0.0, -1.0), direction(0.5, 0.5));
+        assert_close(Vec3::new(1.0, 0.0, 0.0), direction(0.75, 0.5));
+
+        // Both sides of the seam look behind
+        assert_close(Vec3::new(0.0, 0.0, 1.0), direction(0.0, 0.5));
+        assert_close(Vec3::new(0.0, 0.0, 1.0), direction(1.0, 0.5));
+
+        // Poles, whatever the longitude
+        for s in [0.0, 0.3, 0.8] {
+            assert_close(Vec3::new(0.0, 1.0, 0.0), direction(s, 1.0));
+            assert_close(Vec3::new(0.0, -1.0, 0.0), direction(s, 0.0));
+        }
+    }
+}
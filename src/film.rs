@@ -0,0 +1,368 @@
+use std::f64::consts::PI;
+use std::ops::{Range, RangeInclusive};
+use std::sync::Mutex;
+
+use crate::color::{Color, BLACK};
+
+// Pixel reconstruction filter, weighting samples by their offset to the pixel center, in pixels.
+// It's separable, the weight of an offset (x, y) is f(x) * f(y), and zero past radius.
+#[derive(Clone, Copy, Debug)]
+pub enum Filter {
+    Box { radius: f64 },
+    Tent { radius: f64 },
+    Gaussian { radius: f64, sigma: f64 },
+    Mitchell { radius: f64, b: f64, c: f64 }, // Mitchell-Netravali, b = c = 1/3 is a good default
+    Lanczos { radius: f64 },                  // Sinc windowed by a wider sinc, down to radius
+}
+
+impl Default for Filter {
+    // Average of the samples within each pixel
+    fn default() -> Self {
+        Self::Box { radius: 0.5 }
+    }
+}
+
+impl Filter {
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Self::Box { radius }
+            | Self::Tent { radius }
+            | Self::Gaussian { radius, .. }
+            | Self::Mitchell { radius, .. }
+            | Self::Lanczos { radius } => radius,
+        }
+    }
+
+    // Pixels on each side of a pixel its samples may reach
+    pub fn reach(&self) -> usize {
+        (self.radius() + 0.5).ceil() as usize - 1
+    }
+
+    pub fn weight(&self, x: f64, y: f64) -> f64 {
+        self.weight_1d(x) * self.weight_1d(y)
+    }
+
+    fn weight_1d(&self, x: f64) -> f64 {
+        let x = x.abs();
+        if x > self.radius() {
+            return 0.0;
+        }
+
+        match *self {
+            Self::Box { .. } => 1.0,
+            Self::Tent { radius } => radius - x,
+            Self::Gaussian { radius, sigma } => {
+                // Shifted down to reach zero at the radius
+                let gaussian = |x: f64| (-x * x / (2.0 * sigma * sigma)).exp();
+                (gaussian(x) - gaussian(radius)).max(0.0)
+            }
+            Self::Mitchell { radius, b, c } => {
+                let x = 2.0 * x / radius;
+                let polynomial = match x > 1.0 {
+                    true => {
+                        (-b - 6.0 * c) * x * x * x
+                            + (6.0 * b + 30.0 * c) * x * x
+                            + (-12.0 * b - 48.0 * c) * x
+                            + (8.0 * b + 24.0 * c)
+                    }
+                    false => {
+                        (12.0 - 9.0 * b - 6.0 * c) * x * x * x
+                            + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+                            + (6.0 - 2.0 * b)
+                    }
+                };
+                polynomial / 6.0
+            }
+            Self::Lanczos { radius } => sinc(x) * sinc(x / radius),
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    match x.abs() < 1e-5 {
+        true => 1.0,
+        false => (PI * x).sin() / (PI * x),
+    }
+}
+
+// Rectangle of the image to render, in pixels from its top left corner, as in the saved image
+#[derive(Clone, Debug)]
+pub struct CropWindow {
+    pub x: Range<usize>,
+    pub y: Range<usize>,
+}
+
+impl CropWindow {
+    pub fn full(image_width: usize, image_height: usize) -> Self {
+        Self {
+            x: 0..image_width,
+            y: 0..image_height,
+        }
+    }
+
+    // Window grown by margin pixels on each side, within the image
+    pub fn grown(&self, margin: usize, image_width: usize, image_height: usize) -> Self {
+        Self {
+            x: self.x.start.saturating_sub(margin)..(self.x.end + margin).min(image_width),
+            y: self.y.start.saturating_sub(margin)..(self.y.end + margin).min(image_height),
+        }
+    }
+}
+
+// Weighted sum of the samples splatted on a pixel
+#[derive(Clone, Copy, Default)]
+struct FilmPixel {
+    color: Color,
+    weight: f64,
+}
+
+struct FilmRows {
+    pixels: Vec<Vec<FilmPixel>>,
+    rendered: Vec<bool>, // Lines whose samples are in
+}
+
+// Image plane accumulating the samples of the lines as they're rendered.
+//
+// Each sample is splatted on all the pixels whose center is within the radius of the filter,
+// weighted by it, and the pixels end up as the weighted average of their samples. A line is
+// complete once the lines around it, within the radius, are rendered too.
+//
+// The film covers the crop window of the image, its lines are numbered from its bottom. Samples
+// are only taken within it, so its edges miss those from the outside a wide filter would blend,
+// unless the window is grown by the reach of the filter.
+pub struct Film {
+    width: usize,
+    height: usize,
+    origin: (usize, usize), // Bottom left pixel in the image, from its bottom left corner
+    filter: Filter,
+    rows: Mutex<FilmRows>,
+}
+
+impl Film {
+    pub fn new(image_height: usize, crop: &CropWindow, filter: Filter) -> Self {
+        let (width, height) = (crop.x.len(), crop.y.len());
+        let rows = FilmRows {
+            pixels: vec![vec![FilmPixel::default(); width]; height],
+            rendered: vec![false; height],
+        };
+
+        Self {
+            width,
+            height,
+            origin: (crop.x.start, image_height - crop.y.end),
+            filter,
+            rows: Mutex::new(rows),
+        }
+    }
+
+    // Lines within the radius of the filter from line, which its samples may reach
+    fn neighborhood(&self, line: usize) -> RangeInclusive<usize> {
+        let reach = self.filter.reach();
+
+        line.saturating_sub(reach)..=(line + reach).min(self.height - 1)
+    }
+
+    // Local film for the samples of a line, to be added back with add_tile()
+    pub fn tile(&self, line: usize) -> FilmTile {
+        let lines = self.neighborhood(line);
+        let pixels = vec![vec![FilmPixel::default(); self.width]; lines.clone().count()];
+
+        FilmTile {
+            line,
+            lines,
+            origin: self.origin,
+            filter: self.filter,
+            pixels,
+        }
+    }
+
+    // Add the samples of a rendered line. Returns the lines this completes, with their pixels.
+    pub fn add_tile(&self, tile: FilmTile) -> Vec<(usize, Vec<Color>)> {
+        let mut rows = self.rows.lock().unwrap();
+        for (y, tile_row) in tile.lines.clone().zip(tile.pixels) {
+            for (pixel, tile_pixel) in rows.pixels[y].iter_mut().zip(tile_row) {
+                pixel.color += tile_pixel.color;
+                pixel.weight += tile_pixel.weight;
+            }
+        }
+        rows.rendered[tile.line] = true;
+
+        self.neighborhood(tile.line)
+            .filter(|&y| self.neighborhood(y).all(|y| rows.rendered[y]))
+            .map(|y| {
+                let line = rows.pixels[y]
+                    .iter()
+                    .map(|pixel| match pixel.weight != 0.0 {
+                        true => pixel.color / pixel.weight,
+                        false => BLACK,
+                    })
+                    .collect();
+                (y, line)
+            })
+            .collect()
+    }
+}
+
+// Samples of a line of the film, and the lines around it they reach
+pub struct FilmTile {
+    line: usize,
+    lines: RangeInclusive<usize>,
+    origin: (usize, usize),
+    filter: Filter,
+    pixels: Vec<Vec<FilmPixel>>,
+}
+
+impl FilmTile {
+    // Pixels of the line in the image, from its bottom left corner
+    pub fn pixels(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (x, y) = (self.origin.0, self.origin.1 + self.line);
+
+        (x..x + self.pixels[0].len()).map(move |x| (x, y))
+    }
+
+    // Splat a sample at (x, y) of the image, in pixels from its bottom left corner
+    pub fn add_sample(&mut self, x: f64, y: f64, color: Color) {
+        let (x, y) = (x - self.origin.0 as f64, y - self.origin.1 as f64);
+        let radius = self.filter.radius();
+        // Pixels whose center is in (p - radius, p + radius]
+        let covered = |p: f64, pixels: usize| {
+            let start = (p - 0.5 - radius).floor() + 1.0;
+            let end = (p - 0.5 + radius).floor();
+            (start.max(0.0) as usize)..=(end.min(pixels as f64 - 1.0) as usize)
+        };
+        let width = self.pixels[0].len();
+        let lines = covered(y, self.lines.end() + 1);
+        for pixel_y in lines.filter(|pixel_y| self.lines.contains(pixel_y)) {
+            for pixel_x in covered(x, width) {
+                let weight = self
+                    .filter
+                    .weight(pixel_x as f64 + 0.5 - x, pixel_y as f64 + 0.5 - y);
+                let pixel = &mut self.pixels[pixel_y - self.lines.start()][pixel_x];
+                pixel.color += weight * color;
+                pixel.weight += weight;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3::Length;
+
+    #[test]
+    fn test_filters() {
+        let mitchell = Filter::Mitchell {
+            radius: 2.0,
+            b: 1.0 / 3.0,
+            c: 1.0 / 3.0,
+        };
+        assert!((mitchell.weight(0.0, 0.0) - (8.0 / 9.0) * (8.0 / 9.0)).abs() < 1e-12);
+        assert!(mitchell.weight(1.99, 0.0).abs() < 1e-4);
+        assert!(mitchell.weight(1.5, 0.0) < 0.0);
+
+        let lanczos = Filter::Lanczos { radius: 3.0 };
+        assert_eq!(1.0, lanczos.weight(0.0, 0.0));
+        assert!(lanczos.weight(1.0, 0.0).abs() < 1e-12);
+        assert_eq!(0.0, lanczos.weight(0.0, 3.5));
+
+        let gaussian = Filter::Gaussian {
+            radius: 1.5,
+            sigma: 0.5,
+        };
+        assert!(gaussian.weight(0.0, 0.0) > gaussian.weight(0.5, 0.0));
+        assert_eq!(0.0, gaussian.weight(1.5, 0.0));
+
+        assert_eq!(0.25, Filter::Tent { radius: 1.0 }.weight(0.5, 0.5));
+    }
+
+    #[test]
+    fn test_splatting() {
+        // A box filter keeps the samples in their pixel
+        let film = Film::new(3, &CropWindow::full(3, 3), Filter::default());
+        for line in [0, 2] {
+            let mut tile = film.tile(line);
+            tile.add_sample(1.0, line as f64 + 0.2, Color::new(1.0, 1.0, 1.0));
+            assert_eq!(
+                vec![(line, vec![BLACK, Color::new(1.0, 1.0, 1.0), BLACK])],
+                film.add_tile(tile)
+            );
+        }
+
+        // A tent spreads them to the neighbors, and the lines are complete with their neighbors
+        let film = Film::new(3, &CropWindow::full(3, 3), Filter::Tent { radius: 1.0 });
+        let mut tile = film.tile(1);
+        tile.add_sample(1.5, 1.5, Color::new(1.0, 1.0, 1.0));
+        assert!(film.add_tile(tile).is_empty());
+        let completed = film.add_tile(film.tile(0));
+        assert_eq!(
+            vec![0],
+            completed.iter().map(|(y, _)| *y).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![BLACK; 3], completed[0].1);
+        let completed = film.add_tile(film.tile(2));
+        assert_eq!(
+            vec![1, 2],
+            completed.iter().map(|(y, _)| *y).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![BLACK, Color::new(1.0, 1.0, 1.0), BLACK],
+            completed[0].1
+        );
+    }
+
+    #[test]
+    fn test_crop() {
+        // Top right corner of a 4x4 image
+        let crop = CropWindow { x: 2..4, y: 0..1 };
+        let film = Film::new(4, &crop, Filter::default());
+        let mut tile = film.tile(0);
+        assert_eq!(vec![(2, 3), (3, 3)], tile.pixels().collect::<Vec<_>>());
+
+        tile.add_sample(3.5, 3.5, Color::new(1.0, 1.0, 1.0));
+        assert_eq!(
+            vec![(0, vec![BLACK, Color::new(1.0, 1.0, 1.0)])],
+            film.add_tile(tile)
+        );
+    }
+
+    #[test]
+    fn test_crop_margin() {
+        // Samples at fixed places of each pixel, of colors changing from pixel to pixel
+        let render = |film: &Film, lines: usize| {
+            let mut image = vec![vec![]; lines];
+            for line in 0..lines {
+                let mut tile = film.tile(line);
+                for (x, y) in tile.pixels().collect::<Vec<_>>() {
+                    for (dx, dy) in [(0.2, 0.3), (0.7, 0.9)] {
+                        let color = Color::new((x % 3) as f64, (y % 2) as f64, dx);
+                        tile.add_sample(x as f64 + dx, y as f64 + dy, color);
+                    }
+                }
+                for (y, pixels) in film.add_tile(tile) {
+                    image[y] = pixels;
+                }
+            }
+            image
+        };
+        let filter = Filter::Tent { radius: 1.5 };
+        assert_eq!(1, filter.reach());
+        let full = render(&Film::new(6, &CropWindow::full(6, 6), filter), 6);
+
+        // In the middle of the image, and against its top left corner
+        for crop in [
+            CropWindow { x: 2..4, y: 1..4 },
+            CropWindow { x: 0..2, y: 0..1 },
+        ] {
+            let grown = crop.grown(filter.reach(), 6, 6);
+            let cropped = render(&Film::new(6, &grown, filter), grown.y.len());
+            for y in crop.y.clone() {
+                for x in crop.x.clone() {
+                    let difference =
+                        full[5 - y][x] - cropped[grown.y.end - 1 - y][x - grown.x.start];
+                    assert!(difference.length() < 1e-12, "{crop:?} at ({x}, {y})");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{MulAdd, Vec3};
+
+// Mapping from the angle off the optical axis to the distance from the center of the image
+#[derive(Clone, Copy)]
+pub enum FisheyeProjection {
+    Equidistant, // r = f * theta, angles are kept
+    Equisolid,   // r = 2 * f * sin(theta / 2), areas are kept
+}
+
+// Fisheye lens, forming a circular image inscribed in the film height. Its field of view is the
+// angle across the circle, and may go over 180 degrees.
+pub struct FisheyeCamera {
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    max_theta: f64, // Half of the field of view
+    aspect_ratio: f64,
+    projection: FisheyeProjection,
+    shutter: Shutter,
+}
+
+impl FisheyeCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        fov: f64, // Field of view in degrees
+        aspect_ratio: f64,
+        projection: FisheyeProjection,
+    ) -> Self {
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+        let max_theta = (fov / 2.0).to_radians().clamp(0.0, std::f64::consts::PI);
+
+        Self {
+            origin: look_from,
+            u,
+            v,
+            w,
+            max_theta,
+            aspect_ratio,
+            projection,
+            shutter: Shutter::default(),
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        // Film coordinates with the image circle of radius 1
+        let x = (2.0 * s - 1.0) * self.aspect_ratio;
+        let y = 2.0 * t - 1.0;
+        let r = x.hypot(y);
+        if r > 1.0 {
+            return None;
+        }
+
+        let theta = match self.projection {
+            FisheyeProjection::Equidistant => r * self.max_theta,
+            FisheyeProjection::Equisolid => {
+                2.0 * (r * (self.max_theta / 2.0).sin()).clamp(-1.0, 1.0).asin()
+            }
+        };
+        let phi = y.atan2(x);
+
+        // theta away from -w, towards phi on the film plane
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let direction = self.u.mul_add(
+            sin_theta * phi.cos(),
+            self.v.mul_add(sin_theta * phi.sin(), -cos_theta * self.w),
+        );
+
+        Some(Ray::new(self.origin, direction).with_time(self.shutter.sample()))
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3::{Length, Unit};
+
+    fn fisheye(aspect_ratio: f64, projection: FisheyeProjection) -> FisheyeCamera {
+        FisheyeCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            180.0,
+            aspect_ratio,
+            projection,
+        )
+    }
+
+    fn assert_direction(camera: &FisheyeCamera, s: f64, t: f64, expected: Vec3) {
+        let direction = camera.get_ray(s, t).unwrap().direction().unit();
+        assert!(
+            (direction - expected.unit()).length() < 1e-9,
+            "{direction:?}"
+        );
+    }
+
+    #[test]
+    fn test_get_ray() {
+        for projection in [FisheyeProjection::Equidistant, FisheyeProjection::Equisolid] {
+            // Center, and 90 degrees off axis on the rim of the image circle
+            let camera = fisheye(1.0, projection);
+            assert_direction(&camera, 0.5, 0.5, Vec3::new(0.0, 0.0, -1.0));
+            assert_direction(&camera, 1.0, 0.5, Vec3::new(1.0, 0.0, 0.0));
+            assert_direction(&camera, 0.5, 1.0, Vec3::new(0.0, 1.0, 0.0));
+
+            // Nothing in the corners, out of the circle
+            assert!(camera.get_ray(1.0, 1.0).is_none());
+            assert!(camera.get_ray(0.0, 0.0).is_none());
+        }
+
+        // Halfway to the rim, the equidistant projection is halfway to 90 degrees
+        let camera = fisheye(1.0, FisheyeProjection::Equidistant);
+        assert_direction(&camera, 0.75, 0.5, Vec3::new(1.0, 0.0, -1.0));
+
+        // On a wide film, the circle is inscribed in its height
+        let camera = fisheye(2.0, FisheyeProjection::Equidistant);
+        assert_direction(&camera, 0.75, 0.5, Vec3::new(1.0, 0.0, 0.0));
+        assert!(camera.get_ray(0.9, 0.5).is_none());
+    }
+}
@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+use crate::color::WHITE;
+use crate::henyey_greenstein::HenyeyGreenstein;
+use crate::hit_record::HitRecord;
+use crate::ray::Ray;
+use crate::vec3::{Length, Vec3};
+
+// Homogeneous medium filling the whole scene, for haze and god rays
+//
+// Rays scatter at exponentially distributed distances given by the extinction coefficient, the
+// sum of the scattering and absorption ones (per world unit). As both are the same for every
+// color, the only weight left at a scattering event is the albedo scattering / extinction.
+pub struct Fog {
+    extinction: f64,
+    extent: f64, // Distance to the background through the fog
+    phase_function: HenyeyGreenstein,
+}
+
+impl Fog {
+    pub fn new(scattering: f64, absorption: f64, asymmetry: f64, extent: f64) -> Self {
+        let extinction = scattering + absorption;
+        let albedo = match extinction > 0.0 {
+            true => scattering / extinction,
+            false => 0.0,
+        };
+
+        Self {
+            extinction,
+            extent,
+            phase_function: HenyeyGreenstein::new(albedo * WHITE, asymmetry),
+        }
+    }
+
+    // Scattering event of r before the surface hit at t_range.end, if any. Rays that miss
+    // everything go through the fog up to its extent.
+    pub fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        if self.extinction <= 0.0 {
+            return None;
+        }
+
+        let ray_length = r.direction().length();
+        let t_end = match t_range.end.is_finite() {
+            true => t_range.end,
+            false => self.extent / ray_length,
+        };
+        let t = t_range.start - (1.0 - fastrand::f64()).ln() / (self.extinction * ray_length);
+        if t >= t_end {
+            return None;
+        }
+
+        // The normal and the face are meaningless inside a volume
+        let normal = Vec3::new(1.0, 0.0, 0.0);
+
+        Some(HitRecord::new(r.at(t), normal, &self.phase_function, t, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point3::Point3;
+
+    #[test]
+    fn test_extent() {
+        // Thin fog, only 1 unit deep towards the background
+        let fog = Fog::new(0.1, 0.0, 0.0, 1.0);
+        let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -2.0));
+        let scattered = |t_end: f64| {
+            let count = (0..1000)
+                .filter(|_| fog.hit(&r, &(0.001..t_end)).is_some())
+                .count();
+            count as f64 / 1000.0
+        };
+
+        // Up to a surface 100 units away, past the extent, nearly every ray scatters
+        assert!(scattered(50.0) > 0.99);
+        // To the background, about 1 - e^(-0.1)
+        assert!(scattered(f64::INFINITY) < 0.2);
+    }
+}
@@ -0,0 +1,173 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::color::Color;
+
+// Minimal complex number support for the conductor Fresnel equations.
+#[derive(Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn real(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+
+    // |z|^2
+    fn norm(self) -> f64 {
+        self.re.mul_add(self.re, self.im * self.im)
+    }
+
+    fn sqrt(self) -> Self {
+        let n = self.norm().sqrt();
+        if n == 0.0 {
+            return Self::real(0.0);
+        }
+        let t1 = (0.5 * (n + self.re.abs())).sqrt();
+        let t2 = 0.5 * self.im / t1;
+        match self.re >= 0.0 {
+            true => Self::new(t1, t2),
+            false => Self::new(t2.abs(), t1.copysign(self.im)),
+        }
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::new(
+            self.re.mul_add(other.re, -self.im * other.im),
+            self.re.mul_add(other.im, self.im * other.re),
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        let scale = 1.0 / other.norm();
+        Self::new(
+            scale * self.re.mul_add(other.re, self.im * other.im),
+            scale * self.im.mul_add(other.re, -self.re * other.im),
+        )
+    }
+}
+
+// Unpolarized Fresnel reflectance of a dielectric interface.
+//
+// eta is the relative index of refraction n_t / n_i, with cos_theta_i measured on the incident
+// side. Negative cosines mean that the ray arrives from the other side of the interface.
+pub fn fr_dielectric(cos_theta_i: f64, eta: f64) -> f64 {
+    let (cos_theta_i, eta) = match cos_theta_i < 0.0 {
+        true => (-cos_theta_i.max(-1.0), 1.0 / eta),
+        false => (cos_theta_i.min(1.0), eta),
+    };
+
+    // Snell's law: sin^2(theta_t) = sin^2(theta_i) / eta^2
+    let sin2_theta_i = 1.0 - cos_theta_i * cos_theta_i;
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    if sin2_theta_t >= 1.0 {
+        // Total internal reflection
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    let r_parallel = eta.mul_add(cos_theta_i, -cos_theta_t) / eta.mul_add(cos_theta_i, cos_theta_t);
+    let r_perpendicular =
+        eta.mul_add(-cos_theta_t, cos_theta_i) / eta.mul_add(cos_theta_t, cos_theta_i);
+
+    r_parallel.mul_add(r_parallel, r_perpendicular * r_perpendicular) / 2.0
+}
+
+// Fresnel reflectance of a conductor with complex index of refraction eta + i*k.
+fn fr_complex_channel(cos_theta_i: f64, eta: f64, k: f64) -> f64 {
+    let cos_theta_i = Complex::real(cos_theta_i.clamp(0.0, 1.0));
+    let eta = Complex::new(eta, k);
+
+    // Snell's law: sin^2(theta_t) = sin^2(theta_i) / eta^2
+    let sin2_theta_i = Complex::real(1.0) - cos_theta_i * cos_theta_i;
+    let sin2_theta_t = sin2_theta_i / (eta * eta);
+    let cos_theta_t = (Complex::real(1.0) - sin2_theta_t).sqrt();
+
+    let r_parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let r_perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+
+    (r_parallel.norm() + r_perpendicular.norm()) / 2.0
+}
+
+// Per channel Fresnel reflectance of a conductor.
+pub fn fr_complex(cos_theta_i: f64, eta: &Color, k: &Color) -> Color {
+    Color::new(
+        fr_complex_channel(cos_theta_i, eta.x(), k.x()),
+        fr_complex_channel(cos_theta_i, eta.y(), k.y()),
+        fr_complex_channel(cos_theta_i, eta.z(), k.z()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fr_dielectric() {
+        // At normal incidence: ((n - 1) / (n + 1))^2
+        let fresnel = fr_dielectric(1.0, 1.5);
+        assert!((fresnel - 0.04).abs() < 1e-12, "{fresnel}");
+
+        // Same interface seen from the inside
+        let fresnel = fr_dielectric(-1.0, 1.0 / 1.5);
+        assert!((fresnel - 0.04).abs() < 1e-12, "{fresnel}");
+
+        // Total internal reflection
+        assert_eq!(1.0, fr_dielectric(0.5, 1.0 / 1.5));
+
+        // A conductor without absorption is a dielectric
+        for cos_theta_i in [0.1, 0.5, 0.9] {
+            let dielectric = fr_dielectric(cos_theta_i, 1.5);
+            let conductor = fr_complex_channel(cos_theta_i, 1.5, 0.0);
+            assert!((dielectric - conductor).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fr_complex_normal_incidence() {
+        // At normal incidence: ((n - 1)^2 + k^2) / ((n + 1)^2 + k^2)
+        let (n, k) = (0.2, 3.9);
+        let expected = ((n - 1.0) * (n - 1.0) + k * k) / ((n + 1.0) * (n + 1.0) + k * k);
+        let fresnel = fr_complex_channel(1.0, n, k);
+        assert!(
+            (fresnel - expected).abs() < 1e-12,
+            "{fresnel} != {expected}"
+        );
+    }
+
+    #[test]
+    fn test_fr_complex_grazing() {
+        // Every conductor is a perfect mirror at grazing angles
+        let fresnel = fr_complex_channel(0.0, 1.657, 9.224);
+        assert!((fresnel - 1.0).abs() < 1e-12, "{fresnel}");
+    }
+}
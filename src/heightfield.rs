@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::image::Image;
+use crate::material::Material;
+use crate::perlin::Perlin;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Cross, Dot, Unit, Vec3};
+
+// Terrain from a grid of heights
+//
+// The nx * nz height samples span the unit square of the xz plane, with the heights along y.
+// Every cell is split in two triangles, shaded with the normals of the grid interpolated. Rays
+// walk the cells they cross in order with a 2D DDA, so only a line of cells is tested. Place
+// and scale it with an Instance.
+pub struct Heightfield {
+    nx: usize,
+    nz: usize,
+    heights: Vec<f64>, // x first, then z
+    normals: Vec<Vec3>,
+    y_range: Range<f64>,
+    material: Box<dyn Material>,
+}
+
+impl Heightfield {
+    pub fn new(
+        nx: usize,
+        nz: usize,
+        heights: Vec<f64>,
+        material: Box<dyn Material>,
+    ) -> anyhow::Result<Self> {
+        if nx < 2 || nz < 2 {
+            anyhow::bail!("heightfield needs at least 2x2 samples, got {nx}x{nz}");
+        }
+        if heights.len() != nx * nz {
+            anyhow::bail!("{nx}x{nz} heightfield with {} heights", heights.len());
+        }
+        if heights.iter().any(|h| !h.is_finite()) {
+            anyhow::bail!("heightfield with non finite heights");
+        }
+
+        let min = heights.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = heights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        // Vertex normals from the slopes, by central differences
+        let height = |x: usize, z: usize| heights[x + z * nx];
+        let (dx, dz) = (1.0 / (nx - 1) as f64, 1.0 / (nz - 1) as f64);
+        let mut normals = Vec::with_capacity(nx * nz);
+        for z in 0..nz {
+            for x in 0..nx {
+                let (x0, x1) = (x.saturating_sub(1), (x + 1).min(nx - 1));
+                let (z0, z1) = (z.saturating_sub(1), (z + 1).min(nz - 1));
+                let slope_x = (height(x1, z) - height(x0, z)) / ((x1 - x0) as f64 * dx);
+                let slope_z = (height(x, z1) - height(x, z0)) / ((z1 - z0) as f64 * dz);
+                normals.push(Vec3::new(-slope_x, 1.0, -slope_z).unit());
+            }
+        }
+
+        Ok(Self {
+            nx,
+            nz,
+            heights,
+            normals,
+            y_range: min..max,
+            material,
+        })
+    }
+
+    // Heights from the luminance of an image, in [0, 1]. Image rows go along z.
+    pub fn from_image(image: &Image, material: Box<dyn Material>) -> anyhow::Result<Self> {
+        let (nx, nz) = (image.width(), image.height());
+        let heights = (0..nz)
+            .flat_map(|z| (0..nx).map(move |x| (x, z)))
+            .map(|(x, z)| image.luminance(x, z))
+            .collect();
+
+        Self::new(nx, nz, heights, material)
+    }
+
+    // Rolling hills of Perlin turbulence, in [0, 1].
+    pub fn from_noise(
+        resolution: usize,
+        frequency: f64,
+        material: Box<dyn Material>,
+    ) -> anyhow::Result<Self> {
+        const OCTAVES: usize = 6;
+        if resolution < 2 {
+            anyhow::bail!("heightfield needs at least 2x2 samples, got {resolution}x{resolution}");
+        }
+        let noise = Perlin::new();
+        let step = 1.0 / (resolution - 1) as f64;
+        let heights = (0..resolution)
+            .flat_map(|z| (0..resolution).map(move |x| (x, z)))
+            .map(|(x, z)| {
+                let p = frequency * Point3::new(x as f64 * step, 0.0, z as f64 * step);
+                noise.turbulence(&p, OCTAVES).min(1.0)
+            })
+            .collect();
+
+        Self::new(resolution, resolution, heights, material)
+    }
+
+    fn vertex(&self, x: usize, z: usize) -> (Point3, Vec3) {
+        let i = x + z * self.nx;
+        let p = Point3::new(
+            x as f64 / (self.nx - 1) as f64,
+            self.heights[i],
+            z as f64 / (self.nz - 1) as f64,
+        );
+
+        (p, self.normals[i])
+    }
+
+    // Closest hit with the two triangles of the cell, as (t, shading normal).
+    fn hit_cell(&self, r: &Ray, x: usize, z: usize, t_range: &Range<f64>) -> Option<(f64, Vec3)> {
+        let v00 = self.vertex(x, z);
+        let v10 = self.vertex(x + 1, z);
+        let v01 = self.vertex(x, z + 1);
+        let v11 = self.vertex(x + 1, z + 1);
+
+        [(&v00, &v11, &v10), (&v00, &v01, &v11)]
+            .into_iter()
+            .filter_map(|(a, b, c)| {
+                let (t, u, v) = hit_triangle(r, &a.0, &b.0, &c.0)?;
+                let normal = (1.0 - u - v) * a.1 + u * b.1 + v * c.1;
+                t_range.contains(&t).then_some((t, normal))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+    }
+}
+
+// Möller-Trumbore ray-triangle intersection, as (t, barycentric coordinates of b and c).
+fn hit_triangle(r: &Ray, a: &Point3, b: &Point3, c: &Point3) -> Option<(f64, f64, f64)> {
+    let edge1 = b - a;
+    let edge2 = c - a;
+    let p = r.direction().cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() < 1e-12 {
+        return None;
+    }
+
+    let inverse = 1.0 / determinant;
+    let s = r.origin() - a;
+    let u = s.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = r.direction().dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some((edge2.dot(q) * inverse, u, v))
+}
+
+impl Hittable for Heightfield {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Clip the ray to the bounding box
+        let origin = [r.origin().x(), r.origin().y(), r.origin().z()];
+        let direction = [r.direction().x(), r.direction().y(), r.direction().z()];
+        let bounds = [0.0..1.0, self.y_range.clone(), 0.0..1.0];
+        let (mut t_start, mut t_end) = (t_range.start, t_range.end);
+        for axis in 0..3 {
+            let inverse = 1.0 / direction[axis];
+            let t0 = (bounds[axis].start - origin[axis]) * inverse;
+            let t1 = (bounds[axis].end - origin[axis]) * inverse;
+            t_start = t_start.max(t0.min(t1));
+            t_end = t_end.min(t0.max(t1));
+            if t_start > t_end {
+                return None;
+            }
+        }
+
+        // Cell where the ray enters the grid
+        let cells = [(self.nx - 1) as f64, 0.0, (self.nz - 1) as f64];
+        let entry = r.at(t_start);
+        let cell = |axis: usize, value: f64| {
+            ((value * cells[axis]).floor() as i64).clamp(0, cells[axis] as i64 - 1)
+        };
+        let (mut x, mut z) = (cell(0, entry.x()), cell(2, entry.z()));
+
+        // Ray parameter at the next cell boundary on x and z, and across a whole cell
+        let step = |axis: usize, cell: i64| -> (i64, f64, f64) {
+            let size = 1.0 / cells[axis];
+            match direction[axis] {
+                d if d > 0.0 => (1, ((cell + 1) as f64 * size - origin[axis]) / d, size / d),
+                d if d < 0.0 => (-1, (cell as f64 * size - origin[axis]) / d, -size / d),
+                _ => (0, f64::INFINITY, f64::INFINITY),
+            }
+        };
+        let (step_x, mut t_max_x, t_delta_x) = step(0, x);
+        let (step_z, mut t_max_z, t_delta_z) = step(2, z);
+
+        let t_range = t_range.start..t_end;
+        loop {
+            if let Some((t, normal)) = self.hit_cell(r, x as usize, z as usize, &t_range) {
+                let p = r.at(t);
+                let hit_record = HitRecord::new(p, normal.unit(), &*self.material, t, r);
+                return Some(hit_record.with_uv(p.x(), p.z()));
+            }
+
+            // Next cell along the ray
+            let t_next = match t_max_x < t_max_z {
+                true => {
+                    x += step_x;
+                    t_max_x += t_delta_x;
+                    t_max_x - t_delta_x
+                }
+                false => {
+                    z += step_z;
+                    t_max_z += t_delta_z;
+                    t_max_z - t_delta_z
+                }
+            };
+            let outside =
+                !(0..self.nx as i64 - 1).contains(&x) || !(0..self.nz as i64 - 1).contains(&z);
+            if outside || t_next > t_end {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+
+    #[test]
+    fn test_hit() {
+        // A ramp rising along x, h = x, sampled on a 5x3 grid
+        let heights = (0..3)
+            .flat_map(|_| (0..5).map(|x| x as f64 / 4.0))
+            .collect();
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let heightfield = Heightfield::new(5, 3, heights, material).unwrap();
+
+        // Flying low along x, it crosses a few cells before hitting the ramp at x = 0.7
+        let r = Ray::new(Point3::new(-1.0, 0.7, 0.3), Vec3::new(2.0, 0.0, 0.0));
+        let hit_record = heightfield.hit(&r, &(0.0..f64::INFINITY)).unwrap();
+        assert!((hit_record.t() - 0.85).abs() < 1e-9);
+        assert!((hit_record.p().x() - 0.7).abs() < 1e-9);
+        let expected_normal = Vec3::new(-1.0, 1.0, 0.0).unit();
+        assert!((hit_record.normal().dot(expected_normal) - 1.0).abs() < 1e-9);
+
+        // Above it all
+        let r = Ray::new(Point3::new(-1.0, 1.5, 0.3), Vec3::new(1.0, 0.0, 0.0));
+        assert!(heightfield.hit(&r, &(0.0..f64::INFINITY)).is_none());
+    }
+
+    #[test]
+    fn test_from_noise() {
+        let material = || Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        assert!(Heightfield::from_noise(1, 4.0, material()).is_err());
+        assert!(Heightfield::from_noise(0, 4.0, material()).is_err());
+        assert!(Heightfield::from_noise(2, 4.0, material()).is_ok());
+    }
+}
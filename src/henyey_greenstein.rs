@@ -0,0 +1,94 @@
+use std::f64::consts::PI;
+
+use crate::color::Color;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Unit, Vec3};
+
+// Henyey-Greenstein phase function of participating media
+//
+// The asymmetry g is the mean cosine of the scattering angle: positive values scatter forward,
+// like haze around a light, negative values backwards, and 0 is isotropic.
+pub struct HenyeyGreenstein {
+    albedo: Color,
+    g: f64,
+}
+
+impl HenyeyGreenstein {
+    pub fn new(albedo: Color, g: f64) -> Self {
+        let g = g.clamp(-0.99, 0.99);
+        Self { albedo, g }
+    }
+}
+
+// Density over the sphere of scattering with cos_theta between the incoming and outgoing
+// directions of travel.
+fn phase(cos_theta: f64, g: f64) -> f64 {
+    let denominator = 1.0 + g * g - 2.0 * g * cos_theta;
+    (1.0 - g * g) / (4.0 * PI * denominator * denominator.sqrt())
+}
+
+// Inversion of the cumulative distribution of the phase function.
+fn sample_cos_theta(g: f64) -> f64 {
+    let xi = fastrand::f64();
+    if g.abs() < 1e-3 {
+        return 1.0 - 2.0 * xi;
+    }
+
+    let s = (1.0 - g * g) / (1.0 - g + 2.0 * g * xi);
+    ((1.0 + g * g - s * s) / (2.0 * g)).clamp(-1.0, 1.0)
+}
+
+impl Material for HenyeyGreenstein {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        let cos_theta = sample_cos_theta(self.g);
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * fastrand::f64();
+        let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+
+        // The phase function is sampled exactly, only the albedo is left
+        let onb = Onb::new(r_in.direction());
+        let scattered = r_in.spawn(hit_record.p(), onb.to_world(&local));
+
+        Some((scattered, self.albedo))
+    }
+
+    fn scattering_pdf(&self, r_in: &Ray, _hit_record: &HitRecord, scattered: &Ray) -> f64 {
+        let cos_theta = r_in.direction().unit().dot(scattered.direction().unit());
+        phase(cos_theta, self.g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_normalized() {
+        // Integral over the sphere: 2 * PI * integral of phase(cos) dcos in [-1, 1]
+        const STEPS: usize = 100_000;
+        for g in [-0.7, 0.0, 0.3, 0.9] {
+            let dcos = 2.0 / STEPS as f64;
+            let integral: f64 = (0..STEPS)
+                .map(|i| phase(-1.0 + (i as f64 + 0.5) * dcos, g) * dcos)
+                .sum();
+            assert!(
+                (2.0 * PI * integral - 1.0).abs() < 1e-3,
+                "g = {g}: {integral}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mean_cosine() {
+        // The mean cosine of the sampled directions is g
+        fastrand::seed(7);
+        const SAMPLES: usize = 100_000;
+        for g in [-0.5, 0.0, 0.8] {
+            let mean = (0..SAMPLES).map(|_| sample_cos_theta(g)).sum::<f64>() / SAMPLES as f64;
+            assert!((mean - g).abs() < 0.01, "g = {g}: {mean}");
+        }
+    }
+}
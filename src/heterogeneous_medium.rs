@@ -0,0 +1,64 @@
+use std::ops::Range;
+
+use crate::color::Color;
+use crate::density::DensityField;
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::isotropic::Isotropic;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{Length, Vec3};
+
+// Volume of varying density, such as clouds or explosions, filling a closed boundary
+//
+// Free flight distances are sampled with delta tracking: tentative collisions are drawn against
+// the constant majorant density, and each one is accepted as a real scattering event with
+// probability density / majorant. Rejected ones are null collisions and the ray keeps going,
+// which keeps the estimator unbiased whatever the density field is. Densities are per object
+// space unit, place the medium with an Instance to keep its looks.
+pub struct HeterogeneousMedium {
+    boundary: Box<dyn Hittable>,
+    density: Box<dyn DensityField>,
+    phase_function: Box<dyn Material>,
+}
+
+impl HeterogeneousMedium {
+    pub fn new(boundary: Box<dyn Hittable>, density: Box<dyn DensityField>, albedo: Color) -> Self {
+        Self {
+            boundary,
+            density,
+            phase_function: Box::new(Isotropic::new(albedo)),
+        }
+    }
+}
+
+impl Hittable for HeterogeneousMedium {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let majorant = self.density.max_density();
+        if majorant <= 0.0 {
+            return None;
+        }
+
+        // Entry and exit points of the whole line, the ray origin may be inside the volume
+        let entry = self.boundary.hit(r, &(f64::NEG_INFINITY..f64::INFINITY))?;
+        let exit = self.boundary.hit(r, &(entry.t() + 0.0001..f64::INFINITY))?;
+
+        let t_exit = exit.t().min(t_range.end);
+        let mut t = entry.t().max(t_range.start).max(0.0);
+
+        let ray_length = r.direction().length();
+        loop {
+            t -= (1.0 - fastrand::f64()).ln() / (majorant * ray_length);
+            if t >= t_exit {
+                return None;
+            }
+
+            let p = r.at(t);
+            if self.density.density(&p) > fastrand::f64() * majorant {
+                // The normal and the face are meaningless inside a volume
+                let normal = Vec3::new(1.0, 0.0, 0.0);
+                return Some(HitRecord::new(p, normal, &*self.phase_function, t, r));
+            }
+        }
+    }
+}
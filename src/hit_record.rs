@@ -10,6 +10,8 @@ pub struct HitRecord<'a> {
     material: &'a dyn Material,
     t: f64,
     front_face: bool,
+    u: f64, // Surface coordinates
+    v: f64,
 }
 
 impl<'a> HitRecord<'a> {
@@ -27,9 +29,15 @@ impl<'a> HitRecord<'a> {
             material,
             t,
             front_face,
+            u: 0.0,
+            v: 0.0,
         }
     }
 
+    pub fn with_uv(self, u: f64, v: f64) -> Self {
+        Self { u, v, ..self }
+    }
+
     fn set_face_normal(r: &Ray, outward_normal: Vec3) -> (bool, Vec3) {
         match r.direction().dot(outward_normal) < 0_f64 {
             true => {
@@ -55,7 +63,7 @@ impl<'a> HitRecord<'a> {
         &self.normal
     }
 
-    pub fn material(&self) -> &dyn Material {
+    pub fn material(&self) -> &'a dyn Material {
         self.material
     }
 
@@ -66,4 +74,16 @@ impl<'a> HitRecord<'a> {
     pub fn front_face(&self) -> bool {
         self.front_face
     }
+
+    // The normal pointing out of the surface, regardless of the side the ray hit.
+    pub fn outward_normal(&self) -> Vec3 {
+        match self.front_face {
+            true => self.normal,
+            false => -self.normal,
+        }
+    }
+
+    pub fn uv(&self) -> (f64, f64) {
+        (self.u, self.v)
+    }
 }
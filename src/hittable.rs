@@ -1,8 +1,50 @@
 use std::ops::Range;
 
 use crate::hit_record::HitRecord;
+use crate::point3::Point3;
 use crate::ray::Ray;
+use crate::vec3::Vec3;
 
 pub trait Hittable: Send + Sync {
-    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord>;
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>>;
+
+    // Light sampling, for the objects that support it
+    //
+    // Probability density, in solid angle, of sampling direction from origin with random().
+    fn pdf_value(&self, _origin: &Point3, _direction: &Vec3) -> f64 {
+        0.0
+    }
+
+    // Random direction from origin towards the object, None if it can't be sampled.
+    fn random(&self, _origin: &Point3) -> Option<Vec3> {
+        None
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::vec3::RandomUnitSphere;
+
+    // Check that random() only gives directions from origin towards object, with a density
+    // pdf_value() that integrates to 1 over all the directions, by uniform sampling.
+    pub fn check_sampling(object: &dyn Hittable, origin: &Point3) {
+        for _ in 0..1000 {
+            let direction = object.random(origin).unwrap();
+            let r = Ray::new(*origin, direction);
+            assert!(object.hit(&r, &(0.001..f64::INFINITY)).is_some());
+            assert!(object.pdf_value(origin, &direction) > 0.0);
+        }
+
+        const SAMPLES: usize = 100000;
+        let integral = (0..SAMPLES)
+            .map(|_| object.pdf_value(origin, &Vec3::random_unit_sphere()))
+            .sum::<f64>()
+            * 4.0
+            * PI
+            / SAMPLES as f64;
+        assert!((integral - 1.0).abs() < 0.05, "{integral}");
+    }
 }
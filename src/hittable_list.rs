@@ -2,7 +2,9 @@ use std::ops::Range;
 
 use crate::hit_record::HitRecord;
 use crate::hittable::Hittable;
+use crate::point3::Point3;
 use crate::ray::Ray;
+use crate::vec3::Vec3;
 
 #[derive(Default)]
 pub struct HittableList {
@@ -13,10 +15,14 @@ impl HittableList {
     pub fn add(&mut self, object: Box<dyn Hittable>) {
         self.objects.push(object);
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
 }
 
 impl Hittable for HittableList {
-    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord> {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
         let mut closest_hit_record = None;
         let mut closest_t = t_range.end;
         for object in self.objects.iter() {
@@ -30,4 +36,23 @@ impl Hittable for HittableList {
 
         closest_hit_record
     }
+
+    // Every object is picked with the same probability
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        let pdf_sum: f64 = self
+            .objects
+            .iter()
+            .map(|object| object.pdf_value(origin, direction))
+            .sum();
+
+        pdf_sum / self.objects.len() as f64
+    }
+
+    fn random(&self, origin: &Point3) -> Option<Vec3> {
+        if self.objects.is_empty() {
+            return None;
+        }
+        let object = &self.objects[fastrand::usize(..self.objects.len())];
+        object.random(origin)
+    }
 }
@@ -0,0 +1,186 @@
+use std::io::Read;
+
+use anyhow::{bail, Context};
+
+use crate::color::{Color, WHITE};
+
+// RGB image from a Netpbm file, to drive heights, masks and such
+//
+// The values are the raw ones scaled to [0, 1], without any gamma decoding.
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>, // Rows from the top
+}
+
+impl Image {
+    // PGM or PPM image, in plain text (P2, P3) or binary (P5, P6) form.
+    pub fn read(mut reader: impl Read) -> anyhow::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).context("reading image")?;
+
+        // Header: magic, width, height and maximum value, separated by whitespace and comments
+        let mut position = 0;
+        let mut next_token = || -> anyhow::Result<String> {
+            loop {
+                match bytes.get(position) {
+                    Some(b'#') => {
+                        while bytes.get(position).is_some_and(|b| *b != b'\n') {
+                            position += 1;
+                        }
+                    }
+                    Some(b) if b.is_ascii_whitespace() => position += 1,
+                    Some(_) => break,
+                    None => bail!("truncated image header"),
+                }
+            }
+            let start = position;
+            while bytes
+                .get(position)
+                .is_some_and(|b| !b.is_ascii_whitespace())
+            {
+                position += 1;
+            }
+            Ok(String::from_utf8_lossy(&bytes[start..position]).into_owned())
+        };
+
+        let magic = next_token()?;
+        let (channels, binary) = match magic.as_str() {
+            "P2" => (1, false),
+            "P3" => (3, false),
+            "P5" => (1, true),
+            "P6" => (3, true),
+            _ => bail!("unsupported image format {magic:?}"),
+        };
+        let mut next_number = || -> anyhow::Result<usize> {
+            let token = next_token()?;
+            token
+                .parse()
+                .with_context(|| format!("bad number {token:?}"))
+        };
+        let width = next_number()?;
+        let height = next_number()?;
+        let max_value = next_number()?;
+        if width == 0 || height == 0 || !(1..=65535).contains(&max_value) {
+            bail!("bad image header: {width}x{height}, maximum value {max_value}");
+        }
+
+        let Some(count) = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(channels))
+        else {
+            bail!("image too large: {width}x{height}");
+        };
+        // Each sample takes at least a byte, before allocating for them all
+        if count > bytes.len() {
+            bail!("truncated image raster");
+        }
+        let values: Vec<usize> = match binary {
+            false => (0..count)
+                .map(|_| next_number())
+                .collect::<anyhow::Result<_>>()?,
+            true => {
+                // A single whitespace separates the header from the raster
+                let raster = &bytes[(position + 1).min(bytes.len())..];
+                let sample_size = if max_value < 256 { 1 } else { 2 };
+                if count
+                    .checked_mul(sample_size)
+                    .is_none_or(|size| raster.len() < size)
+                {
+                    bail!("truncated image raster");
+                }
+                raster
+                    .chunks_exact(sample_size)
+                    .take(count)
+                    .map(|chunk| match chunk {
+                        [value] => *value as usize,
+                        [high, low] => u16::from_be_bytes([*high, *low]) as usize,
+                        _ => unreachable!(),
+                    })
+                    .collect()
+            }
+        };
+
+        if values.iter().any(|value| *value > max_value) {
+            bail!("image sample above the maximum value {max_value}");
+        }
+
+        let scale = 1.0 / max_value as f64;
+        let pixels = values
+            .chunks_exact(channels)
+            .map(|pixel| match pixel {
+                [gray] => WHITE * (*gray as f64 * scale),
+                [r, g, b] => Color::new(*r as f64, *g as f64, *b as f64) * scale,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path).with_context(|| format!("opening {path}"))?;
+        Self::read(std::io::BufReader::new(file)).with_context(|| format!("loading {path}"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Pixel at column x and row y, from the top left corner.
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        self.pixels[x + y * self.width]
+    }
+
+    // Relative luminance of a pixel.
+    pub fn luminance(&self, x: usize, y: usize) -> f64 {
+        let pixel = self.pixel(x, y);
+        0.2126 * pixel.x() + 0.7152 * pixel.y() + 0.0722 * pixel.z()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_plain() {
+        let image = Image::read(&b"P3\n# comment\n2 1\n255\n255 0 0  0 0 51\n"[..]).unwrap();
+        assert_eq!((2, 1), (image.width(), image.height()));
+        assert_eq!(Color::new(1.0, 0.0, 0.0), image.pixel(0, 0));
+        assert_eq!(
+            Color::new(0.0, 0.0, 51.0) * (1.0 / 255.0),
+            image.pixel(1, 0)
+        );
+    }
+
+    #[test]
+    fn test_read_binary() {
+        let mut bytes = b"P5 2 2 255\n".to_vec();
+        bytes.extend([0, 255, 51, 102]);
+        let image = Image::read(bytes.as_slice()).unwrap();
+        assert!((image.luminance(1, 0) - 1.0).abs() < 1e-12);
+        assert!((image.pixel(1, 1).y() - 0.4).abs() < 1e-12);
+
+        bytes.pop();
+        assert!(Image::read(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        // Samples above the maximum value
+        assert!(Image::read(&b"P2 2 1 100 50 101"[..]).is_err());
+        assert!(Image::read(&b"P5 1 1 100 \xc8"[..]).is_err());
+        // Dimensions overflowing the sample count
+        let huge = format!("P6 {} {} 255 ", usize::MAX, 2);
+        assert!(Image::read(huge.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::transform::Transform;
+use crate::vec3::Vec3;
+
+// Placement of a shared object in the world
+//
+// Rays are moved to object space with the inverse transform, and the hit is brought back to
+// world space. The direction isn't normalized, so t is the same in both spaces.
+pub struct Instance {
+    object: Arc<dyn Hittable>,
+    transform: Transform,
+    motion: Vec3, // World space displacement per unit of time
+}
+
+impl Instance {
+    pub fn new(object: Arc<dyn Hittable>, transform: Transform) -> Self {
+        Self {
+            object,
+            transform,
+            motion: Vec3::default(),
+        }
+    }
+
+    // Instance moving linearly from its transform at time 0 to the transform followed by a
+    // translation of motion at time 1.
+    pub fn moving(object: Arc<dyn Hittable>, transform: Transform, motion: Vec3) -> Self {
+        Self {
+            motion,
+            ..Self::new(object, transform)
+        }
+    }
+}
+
+impl Hittable for Instance {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Undo the motion first, it's a translation after the transform
+        let offset = r.time() * self.motion;
+        let inverse = self.transform.inverse();
+        let origin = inverse.point(&(r.origin() - offset));
+        let object_ray = r.spawn(origin, inverse.vector(r.direction()));
+        let hit_record = self.object.hit(&object_ray, t_range)?;
+
+        let p = self.transform.point(&hit_record.p()) + offset;
+        let outward_normal = self.transform.normal(&hit_record.outward_normal());
+        let (u, v) = hit_record.uv();
+
+        Some(
+            HitRecord::new(p, outward_normal, hit_record.material(), hit_record.t(), r)
+                .with_uv(u, v),
+        )
+    }
+
+    // Exact for rigid transforms and uniform scaling, which preserve solid angles. Moving
+    // instances are sampled at time 0.
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        let inverse = self.transform.inverse();
+        self.object
+            .pdf_value(&inverse.point(origin), &inverse.vector(direction))
+    }
+
+    fn random(&self, origin: &Point3) -> Option<Vec3> {
+        let direction = self
+            .object
+            .random(&self.transform.inverse().point(origin))?;
+        Some(self.transform.vector(&direction))
+    }
+}
@@ -0,0 +1,31 @@
+use std::f64::consts::PI;
+
+use crate::color::Color;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::vec3::{RandomUnitVector, Vec3};
+
+// Phase function of participating media, scattering uniformly in all directions.
+pub struct Isotropic {
+    albedo: Color,
+}
+
+impl Isotropic {
+    pub fn new(albedo: Color) -> Self {
+        Self { albedo }
+    }
+}
+
+impl Material for Isotropic {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        let scattered = r_in.spawn(hit_record.p(), Vec3::random_unit_vector());
+
+        Some((scattered, self.albedo))
+    }
+
+    fn scattering_pdf(&self, _r_in: &Ray, _hit_record: &HitRecord, _scattered: &Ray) -> f64 {
+        // Uniform sphere sampling
+        1.0 / (4.0 * PI)
+    }
+}
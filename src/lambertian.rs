@@ -1,8 +1,10 @@
+use std::f64::consts::PI;
+
 use crate::color::Color;
 use crate::hit_record::HitRecord;
 use crate::material::Material;
 use crate::ray::Ray;
-use crate::vec3::{RandomInHemisphere, Vec3, Zero};
+use crate::vec3::{Dot, RandomInHemisphere, Vec3, Zero};
 
 pub struct Lambertian {
     albedo: Color,
@@ -15,16 +17,24 @@ impl Lambertian {
 }
 
 impl Material for Lambertian {
-    fn scatter(&self, _r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
         // let mut scatter_direction = hit_record.normal() + Vec3::random_unit_vector();
         let mut scatter_direction = Vec3::random_in_hemisphere(hit_record.normal());
         if scatter_direction.is_zero() {
             scatter_direction = *hit_record.normal();
         }
 
-        let scattered = Ray::new(hit_record.p(), scatter_direction);
+        let scattered = r_in.spawn(hit_record.p(), scatter_direction);
         let attenuation = self.albedo;
 
         Some((scattered, attenuation))
     }
+
+    fn scattering_pdf(&self, _r_in: &Ray, hit_record: &HitRecord, scattered: &Ray) -> f64 {
+        // Uniform hemisphere sampling
+        match scattered.direction().dot(hit_record.normal()) > 0.0 {
+            true => 1.0 / (2.0 * PI),
+            false => 0.0,
+        }
+    }
 }
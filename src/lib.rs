@@ -2,21 +2,64 @@
 
 use std::thread;
 
+use anyhow::bail;
+
+mod aperture;
 mod buffer;
 mod camera;
+mod camera_path;
 mod color;
+mod conductor;
+mod cone;
+mod constant_medium;
+mod csg;
+mod cuboid;
+mod cylinder;
 mod dark_magic;
+mod density;
 mod dielectric;
+mod diffuse_light;
+mod disk;
+mod equirectangular_camera;
+mod film;
+mod fisheye_camera;
+mod fog;
+mod fresnel;
+mod heightfield;
+mod henyey_greenstein;
+mod heterogeneous_medium;
 mod hit_record;
 mod hittable;
 mod hittable_list;
+mod image;
+mod instance;
+mod isotropic;
 mod lambertian;
+mod mat4;
 mod material;
 mod metal;
+mod microfacet;
+mod omni_stereo_camera;
+mod onb;
+mod orthographic_camera;
+mod perlin;
+mod physical_camera;
+mod plane;
 mod point3;
+mod polynomial;
+mod principled;
+mod quad;
 mod ray;
+mod realistic_camera;
+mod rough_dielectric;
 pub mod scene;
+mod sdf;
+mod spectrum;
 mod sphere;
+mod stereo_camera;
+mod thin_lens_camera;
+mod torus;
+mod transform;
 mod vec3;
 
 #[cfg(feature = "simd")]
@@ -25,36 +68,101 @@ mod simd_vec3;
 #[cfg(not(feature = "simd"))]
 mod scalar_vec3;
 
-use crate::buffer::Buffer;
-use crate::color::{Color, BLACK, WHITE};
+pub use crate::buffer::Buffer;
+pub use crate::film::{CropWindow, Filter};
+
+use crate::color::{Color, BLACK};
+use crate::film::{Film, FilmTile};
+use crate::hit_record::HitRecord;
 use crate::hittable::Hittable;
-use crate::hittable_list::HittableList;
 use crate::ray::Ray;
 use crate::scene::Scene;
-use crate::vec3::{MulAdd, SquareRoot, Unit};
+use crate::spectrum::{SampledSpectrum, SampledWavelengths};
+use crate::vec3::SquareRoot;
 
-fn ray_color(r: &Ray, world: &HittableList, depth: i8) -> Color {
+fn ray_color(r: &Ray, scene: &Scene, depth: i8) -> Color {
     if depth <= 0 {
         return BLACK;
     }
 
-    let t_range = 0.001..std::f64::INFINITY;
-    if let Some(hit_record) = world.hit(r, &t_range) {
+    let t_range = 0.001..f64::INFINITY;
+    if let Some(hit_record) = scene.hit(r, &t_range) {
         //if hit_record.t() < 0.001 {
         //    // Ray hit too close
         //    return BLACK;
         //}
+        let emitted = hit_record.material().emitted(&hit_record);
         if let Some((scattered, attenuation)) = hit_record.material().scatter(r, &hit_record) {
-            return attenuation * ray_color(&scattered, world, depth - 1);
+            let (scattered, attenuation) =
+                sample_lights(scene, r, &hit_record, scattered, attenuation);
+            return emitted + attenuation * ray_color(&scattered, scene, depth - 1);
         }
-        return BLACK;
+        return emitted;
     }
 
-    let unit_direction = r.direction().unit();
-    let t = 0.5 * (unit_direction.y() + 1.0);
+    scene.background(r)
+}
+
+// Spectral version of ray_color(). The RGB colors are upsampled at the wavelengths carried by the
+// path, and the radiance is weighted by the probability of each wavelength.
+fn ray_color_spectral(r: &Ray, scene: &Scene, depth: i8) -> SampledSpectrum {
+    let black = SampledSpectrum::new(0.0);
+    let Some(wavelengths) = r.wavelengths() else {
+        return black;
+    };
+    if depth <= 0 {
+        return black;
+    }
 
-    // (1.0 - t) * WHITE + t * Color::new(0.5, 0.7, 1.0)
-    WHITE.mul_add(1.0 - t, t * Color::new(0.5, 0.7, 1.0))
+    let t_range = 0.001..f64::INFINITY;
+    if let Some(hit_record) = scene.hit(r, &t_range) {
+        let emitted = hit_record.material().emitted(&hit_record);
+        let emitted = wavelengths.weight(&wavelengths.spectrum_from_rgb(&emitted));
+        if let Some((scattered, attenuation)) = hit_record.material().scatter(r, &hit_record) {
+            let (scattered, attenuation) =
+                sample_lights(scene, r, &hit_record, scattered, attenuation);
+            let attenuation = wavelengths.spectrum_from_rgb(&attenuation);
+            return emitted + attenuation * ray_color_spectral(&scattered, scene, depth - 1);
+        }
+        return emitted;
+    }
+
+    wavelengths.weight(&wavelengths.spectrum_from_rgb(&scene.background(r)))
+}
+
+// Importance sample the lights of the scene from diffuse surfaces.
+//
+// Half of the time the scattered ray is replaced by one towards a random light, and the
+// attenuation is reweighted by the mixture of both probability densities.
+fn sample_lights(
+    scene: &Scene,
+    r_in: &Ray,
+    hit_record: &HitRecord,
+    scattered: Ray,
+    attenuation: Color,
+) -> (Ray, Color) {
+    let material = hit_record.material();
+    let lights = scene.lights();
+    if lights.is_empty() || material.scattering_pdf(r_in, hit_record, &scattered) == 0.0 {
+        return (scattered, attenuation);
+    }
+
+    let light_direction = match fastrand::bool() {
+        true => lights.random(&hit_record.p()),
+        false => None,
+    };
+    let scattered = match light_direction {
+        Some(direction) => r_in.spawn(hit_record.p(), direction),
+        None => scattered,
+    };
+    let scattering_pdf = material.scattering_pdf(r_in, hit_record, &scattered);
+    if scattering_pdf == 0.0 {
+        return (scattered, BLACK);
+    }
+    let light_pdf = lights.pdf_value(&hit_record.p(), scattered.direction());
+    let pdf = 0.5 * light_pdf + 0.5 * scattering_pdf;
+
+    (scattered, attenuation * scattering_pdf / pdf)
 }
 
 pub fn rtx(
@@ -64,7 +172,135 @@ pub fn rtx(
     samples_per_pixel: u16,
     depth: i8,
 ) -> anyhow::Result<Buffer> {
-    let buffer = Buffer::new(image_width, image_height);
+    let crop = CropWindow::full(image_width, image_height);
+    render(
+        &scene,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        depth,
+        &crop,
+    )
+}
+
+// Render the crop window of the image only, into a buffer of its size. See Buffer::paste() to
+// put it back into the full image. The pixels around it within the reach of the filter are
+// rendered too, for the samples they blend into its edges, then left out.
+pub fn rtx_crop(
+    scene: Scene,
+    image_width: usize,
+    image_height: usize,
+    samples_per_pixel: u16,
+    depth: i8,
+    crop: &CropWindow,
+) -> anyhow::Result<Buffer> {
+    if crop.x.is_empty() || crop.y.is_empty() {
+        bail!("empty crop window {crop:?}");
+    }
+    if crop.x.end > image_width || crop.y.end > image_height {
+        bail!("crop window {crop:?} outside of the {image_width}x{image_height} image");
+    }
+
+    let grown = crop.grown(scene.filter().reach(), image_width, image_height);
+    let buffer = render(
+        &scene,
+        image_width,
+        image_height,
+        samples_per_pixel,
+        depth,
+        &grown,
+    )?;
+
+    buffer.crop(&CropWindow {
+        x: crop.x.start - grown.x.start..crop.x.end - grown.x.start,
+        y: crop.y.start - grown.y.start..crop.y.end - grown.y.start,
+    })
+}
+
+// Render the pixel at (x, y) of the image, from its top left corner, on the current thread. As
+// with rtx_crop(), the pixels around it within the reach of the filter are rendered too.
+pub fn rtx_pixel(
+    scene: Scene,
+    image_width: usize,
+    image_height: usize,
+    x: usize,
+    y: usize,
+    samples_per_pixel: u16,
+    depth: i8,
+) -> anyhow::Result<Color> {
+    if x >= image_width || y >= image_height {
+        bail!("pixel ({x}, {y}) outside of the {image_width}x{image_height} image");
+    }
+
+    let crop = CropWindow {
+        x: x..x + 1,
+        y: y..y + 1,
+    };
+    let grown = crop.grown(scene.filter().reach(), image_width, image_height);
+    let film = Film::new(image_height, &grown, scene.filter());
+    // Line of the pixel in the film, from its bottom
+    let pixel_line = grown.y.end - 1 - y;
+    let mut pixel = BLACK;
+    for line in 0..grown.y.len() {
+        let mut tile = film.tile(line);
+        rtx_line(
+            &scene,
+            image_width,
+            image_height,
+            samples_per_pixel,
+            depth,
+            &mut tile,
+        );
+        for (line, pixels) in film.add_tile(tile) {
+            if line == pixel_line {
+                pixel = pixels[x - grown.x.start];
+            }
+        }
+    }
+
+    Ok(develop(&scene, vec![pixel])[0])
+}
+
+// Render every frame of the camera path of the scene, saved as prefix0000.ppm, prefix0001.ppm...
+pub fn rtx_sequence(
+    mut scene: Scene,
+    image_width: usize,
+    image_height: usize,
+    samples_per_pixel: u16,
+    depth: i8,
+    prefix: &str,
+) -> anyhow::Result<()> {
+    let Some(frames) = scene.frames() else {
+        bail!("scene without camera path");
+    };
+    for frame in frames {
+        println!("Frame {frame}");
+        scene.set_frame(frame);
+        let crop = CropWindow::full(image_width, image_height);
+        let buffer = render(
+            &scene,
+            image_width,
+            image_height,
+            samples_per_pixel,
+            depth,
+            &crop,
+        )?;
+        buffer.save(&format!("{prefix}{frame:04}.ppm"))?;
+    }
+
+    Ok(())
+}
+
+fn render(
+    scene: &Scene,
+    image_width: usize,
+    image_height: usize,
+    samples_per_pixel: u16,
+    depth: i8,
+    crop: &CropWindow,
+) -> anyhow::Result<Buffer> {
+    let buffer = Buffer::new(crop.x.len(), crop.y.len());
+    let film = Film::new(image_height, crop, scene.filter());
 
     let num_threads = std::thread::available_parallelism()?.get();
     println!("Spawning {num_threads} threads");
@@ -76,17 +312,21 @@ pub fn rtx(
             let thread = s.spawn(|| {
                 let mut lines_drawed = 0;
                 loop {
-                    if let Some((height, mut line)) = buffer.get_line() {
+                    if let Some((height, _)) = buffer.get_line() {
+                        let mut tile = film.tile(height);
                         rtx_line(
-                            &scene,
+                            scene,
                             image_width,
                             image_height,
                             samples_per_pixel,
                             depth,
-                            &mut line,
-                            height,
+                            &mut tile,
                         );
-                        buffer.push_line(height, line);
+                        // The filter may spread the samples over the lines around, which are
+                        // only complete once those are rendered too
+                        for (height, line) in film.add_tile(tile) {
+                            buffer.push_line(height, develop(scene, line));
+                        }
                         lines_drawed += 1;
                     } else {
                         return lines_drawed;
@@ -121,22 +361,92 @@ fn rtx_line(
     image_height: usize,
     samples_per_pixel: u16,
     depth: i8,
-    line: &mut Vec<Color>,
-    height: usize,
+    tile: &mut FilmTile,
 ) {
-    for width in 0..image_width {
-        let mut pixel_color = BLACK;
+    for (width, height) in tile.pixels() {
         for _sample in 0..samples_per_pixel {
-            let u: f64 = (width as f64 + fastrand::f64()) / (image_width as f64 - 1_f64);
-            let v: f64 = (height as f64 + fastrand::f64()) / (image_height as f64 - 1_f64);
+            let (x, y) = (
+                width as f64 + fastrand::f64(),
+                height as f64 + fastrand::f64(),
+            );
+            let u: f64 = x / (image_width as f64 - 1_f64);
+            let v: f64 = y / (image_height as f64 - 1_f64);
 
-            let ray = scene.camera().get_ray(u, v);
-            let sample_pixel_color = ray_color(&ray, scene.world(), depth);
-            pixel_color += sample_pixel_color;
+            let Some(ray) = scene.camera().get_ray(u, v) else {
+                tile.add_sample(x, y, BLACK);
+                continue;
+            };
+            let sample_pixel_color = match scene.spectral() {
+                true => {
+                    let wavelengths = SampledWavelengths::sample();
+                    let ray = ray.with_wavelengths(wavelengths);
+                    let radiance = ray_color_spectral(&ray, scene, depth);
+                    wavelengths.radiance_to_rgb(&radiance)
+                }
+                false => ray_color(&ray, scene, depth),
+            };
+            tile.add_sample(x, y, sample_pixel_color);
         }
+    }
+}
+
+// Final pixel values of a line of the film
+fn develop(scene: &Scene, line: Vec<Color>) -> Vec<Color> {
+    let exposure = scene.camera().exposure();
+    line.into_iter()
+        .map(|pixel_color| {
+            // Spectral samples may fall outside of the RGB gamut, and filters may have negative
+            // lobes, clip them once averaged.
+            let pixel_color = Color::new(
+                pixel_color.x().max(0.0),
+                pixel_color.y().max(0.0),
+                pixel_color.z().max(0.0),
+            );
+
+            // Expose and gamma-correct for gamma=2.0.
+            (exposure * pixel_color).square_root()
+        })
+        .collect()
+}
 
-        // Divide the color by the number of samples and gamma-correct for gamma=2.0.
-        let calibrated_pixel_color = (pixel_color / samples_per_pixel as f64).square_root();
-        line.push(calibrated_pixel_color);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::tests::half_lit;
+    use crate::vec3::Length;
+
+    // Half lit scene, with a filter reaching a pixel past the edge of the light
+    fn scene() -> Scene {
+        let mut scene = half_lit(8.0);
+        scene.set_filter(Filter::Box { radius: 1.5 });
+        scene
+    }
+
+    #[test]
+    fn test_rtx_pixel() {
+        assert!(rtx_pixel(scene(), 8, 8, 8, 0, 1, 2).is_err());
+        assert!(rtx_pixel(scene(), 8, 8, 0, 8, 1, 2).is_err());
+
+        // The first dark pixel gets the samples of the lit one next to it
+        let full = rtx(scene(), 8, 8, 256, 2).unwrap();
+        let pixel = rtx_pixel(scene(), 8, 8, 4, 3, 256, 2).unwrap();
+        assert!(full.pixel(4, 3).x() > 0.2);
+        assert!((pixel - full.pixel(4, 3)).length() < 0.06, "{pixel:?}");
+    }
+
+    #[test]
+    fn test_rtx_crop() {
+        let crop = CropWindow { x: 4..7, y: 2..6 };
+        assert!(rtx_crop(scene(), 8, 8, 1, 2, &CropWindow { x: 4..9, y: 2..6 }).is_err());
+
+        // Same as the full image, the edge of the light included
+        let full = rtx(scene(), 8, 8, 256, 2).unwrap();
+        let cropped = rtx_crop(scene(), 8, 8, 256, 2, &crop).unwrap();
+        for x in crop.x.clone() {
+            for y in crop.y.clone() {
+                let pixel = cropped.pixel(x - crop.x.start, y - crop.y.start);
+                assert!((pixel - full.pixel(x, y)).length() < 0.06, "({x}, {y})");
+            }
+        }
     }
 }
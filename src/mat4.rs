@@ -0,0 +1,164 @@
+use std::ops::Mul;
+
+use crate::dark_magic::forward_ref_binop;
+use crate::point3::Point3;
+use crate::vec3::Vec3;
+
+// 4x4 matrix of affine transforms, in row-major order.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct Mat4 {
+    m: [[f64; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Self = Self::new([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    pub const fn new(m: [[f64; 4]; 4]) -> Self {
+        Self { m }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.m[j][i];
+            }
+        }
+
+        Self::new(m)
+    }
+
+    // Gauss-Jordan elimination with partial pivoting. None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut a = self.m;
+        let mut inverse = Self::IDENTITY.m;
+
+        for column in 0..4 {
+            // Bring up the row with the biggest pivot
+            let pivot = (column..4)
+                .max_by(|i, j| a[*i][column].abs().total_cmp(&a[*j][column].abs()))
+                .unwrap();
+            if a[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(column, pivot);
+            inverse.swap(column, pivot);
+
+            // Normalize the pivot row
+            let scale = 1.0 / a[column][column];
+            for j in 0..4 {
+                a[column][j] *= scale;
+                inverse[column][j] *= scale;
+            }
+
+            // Eliminate the column from every other row
+            for row in 0..4 {
+                if row == column {
+                    continue;
+                }
+                let factor = a[row][column];
+                for j in 0..4 {
+                    a[row][j] -= factor * a[column][j];
+                    inverse[row][j] -= factor * inverse[column][j];
+                }
+            }
+        }
+
+        Some(Self::new(inverse))
+    }
+
+    // M * (p, 1)
+    pub fn transform_point(&self, p: &Point3) -> Point3 {
+        let m = &self.m;
+        let row = |i: usize| m[i][0] * p.x() + m[i][1] * p.y() + m[i][2] * p.z() + m[i][3];
+        let w = row(3);
+        let p = Point3::new(row(0), row(1), row(2));
+
+        match w == 1.0 {
+            true => p,
+            false => p / w,
+        }
+    }
+
+    // M * (v, 0)
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        let m = &self.m;
+        let row = |i: usize| m[i][0] * v.x() + m[i][1] * v.y() + m[i][2] * v.z();
+
+        Vec3::new(row(0), row(1), row(2))
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+
+        Self::new(m)
+    }
+}
+forward_ref_binop! { impl Mul, mul for Mat4, Mat4 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: &Mat4, b: &Mat4) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((a.m[i][j] - b.m[i][j]).abs() < 1e-12, "{a:?} != {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = Mat4::new([
+            [2.0, 0.0, 1.0, 3.0],
+            [0.0, 0.0, 4.0, -1.0],
+            [1.0, 3.0, 0.0, 2.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let inverse = m.inverse().unwrap();
+        assert_near(&(m * inverse), &Mat4::IDENTITY);
+        assert_near(&(inverse * m), &Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn test_singular() {
+        let m = Mat4::new([[1.0; 4]; 4]);
+        assert_eq!(None, m.inverse());
+    }
+
+    #[test]
+    fn test_transform() {
+        let translation = Mat4::new([
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 2.0],
+            [0.0, 0.0, 1.0, 3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let v = Vec3::new(1.0, 1.0, 1.0);
+
+        // Points move, vectors don't
+        assert_eq!(Point3::new(2.0, 3.0, 4.0), translation.transform_point(&v));
+        assert_eq!(v, translation.transform_vector(&v));
+    }
+}
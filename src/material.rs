@@ -1,7 +1,20 @@
-use crate::color::Color;
+use crate::color::{Color, BLACK};
 use crate::hit_record::HitRecord;
 use crate::ray::Ray;
 
 pub trait Material: Send + Sync {
     fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)>;
+
+    fn emitted(&self, _hit_record: &HitRecord) -> Color {
+        BLACK
+    }
+
+    // Probability density, in solid angle, of scatter() choosing the direction of scattered.
+    //
+    // Only diffuse materials report it. Their scattered rays can then be importance sampled
+    // towards the lights of the scene, weighting the attenuation by
+    // scattering_pdf(scattered) / pdf(scattered).
+    fn scattering_pdf(&self, _r_in: &Ray, _hit_record: &HitRecord, _scattered: &Ray) -> f64 {
+        0.0
+    }
 }
@@ -20,7 +20,7 @@ impl Material for Metal {
     fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
         let reflected = r_in.direction().reflect(hit_record.normal());
         let fuzzines = self.fuzz * Vec3::random_unit_sphere();
-        let scattered = Ray::new(hit_record.p(), reflected + fuzzines);
+        let scattered = r_in.spawn(hit_record.p(), reflected + fuzzines);
         let attenuation = self.albedo;
 
         if scattered.direction().dot(hit_record.normal()) > 0_f64 {
@@ -0,0 +1,118 @@
+use std::f64::consts::PI;
+
+use crate::vec3::{Cross, Unit, Vec3};
+
+// Trowbridge-Reitz (GGX) microfacet distribution
+//
+// All the directions are in the local shading frame (see Onb), with the macro surface normal
+// being the z axis.
+#[derive(Clone)]
+pub struct TrowbridgeReitz {
+    alpha: f64,
+}
+
+impl TrowbridgeReitz {
+    // Below this alpha the surface is treated as a perfect mirror.
+    const SMOOTH_ALPHA: f64 = 1e-3;
+
+    pub fn new(roughness: f64) -> Self {
+        // Perceptually linear roughness, as in the Disney and glTF material models.
+        let roughness = roughness.clamp(0.0, 1.0);
+        let alpha = roughness * roughness;
+
+        Self { alpha }
+    }
+
+    pub fn effectively_smooth(&self) -> bool {
+        self.alpha < Self::SMOOTH_ALPHA
+    }
+
+    // Smith's auxiliary function: Λ(w)
+    fn lambda(&self, w: &Vec3) -> f64 {
+        let cos2_theta = w.z() * w.z();
+        if cos2_theta == 0.0 {
+            return f64::INFINITY;
+        }
+        let tan2_theta = (1.0 - cos2_theta).max(0.0) / cos2_theta;
+        let alpha2 = self.alpha * self.alpha;
+
+        ((1.0 + alpha2 * tan2_theta).sqrt() - 1.0) / 2.0
+    }
+
+    // Masking function: G1(w)
+    pub fn g1(&self, w: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    // Height-correlated masking-shadowing function: G(wo, wi)
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    // Sample a microfacet normal from the distribution of normals visible from w.
+    //
+    // Heitz, "Sampling the GGX Distribution of Visible Normals", JCGT 2018.
+    pub fn sample_wm(&self, w: &Vec3) -> Vec3 {
+        // Transform w to the hemispherical configuration
+        let mut wh = Vec3::new(self.alpha * w.x(), self.alpha * w.y(), w.z()).unit();
+        if wh.z() < 0.0 {
+            wh = -wh;
+        }
+
+        // Orthonormal basis for the visible normal sampling
+        let t1 = match wh.z() < 0.99999 {
+            true => Vec3::new(0.0, 0.0, 1.0).cross(wh).unit(),
+            false => Vec3::new(1.0, 0.0, 0.0),
+        };
+        let t2 = wh.cross(t1);
+
+        // Uniformly distributed point on the unit disk
+        let r = fastrand::f64().sqrt();
+        let phi = 2.0 * PI * fastrand::f64();
+        let px = r * phi.cos();
+        let mut py = r * phi.sin();
+
+        // Warp the disk to the visible hemisphere projection
+        let h = (1.0 - px * px).sqrt();
+        let s = (1.0 + wh.z()) / 2.0;
+        py = (1.0 - s) * h + s * py;
+        let pz = (1.0 - px * px - py * py).max(0.0).sqrt();
+
+        // Reproject onto the hemisphere and transform back to the ellipsoid configuration
+        let nh = px * t1 + py * t2 + pz * wh;
+
+        Vec3::new(self.alpha * nh.x(), self.alpha * nh.y(), nh.z().max(1e-6)).unit()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masking() {
+        let distribution = TrowbridgeReitz::new(0.5);
+
+        // Nothing is masked when looking straight down the normal
+        let normal = Vec3::new(0.0, 0.0, 1.0);
+        assert_eq!(1.0, distribution.g1(&normal));
+
+        // Masking increases towards grazing angles
+        let wo = Vec3::new(0.6, 0.0, 0.8);
+        let grazing = Vec3::new(0.99, 0.0, 0.141).unit();
+        assert!(distribution.g1(&grazing) < distribution.g1(&wo));
+
+        // Shadowing-masking is never greater than masking alone
+        assert!(distribution.g(&wo, &grazing) <= distribution.g1(&wo));
+    }
+
+    #[test]
+    fn test_sample_wm_upper_hemisphere() {
+        let distribution = TrowbridgeReitz::new(0.7);
+        let w = Vec3::new(0.6, 0.0, 0.8);
+        for _ in 0..100 {
+            let wm = distribution.sample_wm(&w);
+            assert!(wm.z() > 0.0, "{wm:?}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::f64::consts::PI;
+
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::equirectangular_camera::spherical_direction;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::stereo_camera::{Eye, StereoLayout};
+use crate::vec3::{MulAdd, Vec3};
+
+// Omni-directional stereo (ODS), for stereo VR panoramas
+//
+// Each eye is an equirectangular panorama, whose rays start on a circle of diameter interocular
+// around look_from, tangent to it: every direction is seen as if the head turned towards it.
+// The circle shrinks towards the poles, where there's no sensible stereo, to avoid distortions.
+pub struct OmniStereoCamera {
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    interocular: f64,
+    layout: StereoLayout,
+    shutter: Shutter,
+}
+
+impl OmniStereoCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        interocular: f64,
+        layout: StereoLayout,
+    ) -> Self {
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+
+        Self {
+            origin: look_from,
+            u,
+            v,
+            w,
+            interocular,
+            layout,
+            shutter: Shutter::default(),
+        }
+    }
+}
+
+impl Camera for OmniStereoCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        let (eye, s, t) = self.layout.split(s, t);
+        let phi = (s - 0.5) * 2.0 * PI;
+        let theta = (t - 0.5) * PI;
+        let direction = spherical_direction(&self.u, &self.v, &self.w, phi, theta);
+
+        // The eyes are to the sides of the horizontal view direction
+        let side = match eye {
+            Eye::Left => -1.0,
+            Eye::Right => 1.0,
+        };
+        let right = self.u.mul_add(phi.cos(), phi.sin() * self.w);
+        let offset = side * self.interocular / 2.0 * theta.cos();
+        let origin = right.mul_add(offset, self.origin);
+
+        Some(Ray::new(origin, direction).with_time(self.shutter.sample()))
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
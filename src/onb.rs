@@ -0,0 +1,36 @@
+use crate::vec3::{Cross, Dot, MulAdd, Unit, Vec3};
+
+// Orthonormal basis
+//
+// Local shading frame built around a normal. In local coordinates the normal is the z axis, so
+// cos(theta) of a direction is just its z component.
+pub struct Onb {
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl Onb {
+    pub fn new(normal: &Vec3) -> Self {
+        let w = normal.unit();
+        let a = match w.x().abs() > 0.9 {
+            true => Vec3::new(0.0, 1.0, 0.0),
+            false => Vec3::new(1.0, 0.0, 0.0),
+        };
+        let v = w.cross(a).unit();
+        let u = w.cross(v);
+
+        Self { u, v, w }
+    }
+
+    // World to local coordinates
+    pub fn to_local(&self, a: &Vec3) -> Vec3 {
+        Vec3::new(a.dot(self.u), a.dot(self.v), a.dot(self.w))
+    }
+
+    // Local to world coordinates
+    pub fn to_world(&self, a: &Vec3) -> Vec3 {
+        // a.x() * u + a.y() * v + a.z() * w
+        self.u.mul_add(a.x(), self.v.mul_add(a.y(), a.z() * self.w))
+    }
+}
@@ -0,0 +1,83 @@
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{MulAdd, Vec3};
+
+// Parallel projection, for technical views without perspective. All rays share the direction
+// of view, from a film of the given height in world units.
+pub struct OrthographicCamera {
+    lower_left_corner: Point3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    direction: Vec3,
+    shutter: Shutter,
+}
+
+impl OrthographicCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        view_height: f64,
+        aspect_ratio: f64,
+    ) -> Self {
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+
+        let horizontal = aspect_ratio * view_height * u;
+        let vertical = view_height * v;
+        let lower_left_corner = look_from - (horizontal + vertical) / 2.0;
+
+        Self {
+            lower_left_corner,
+            horizontal,
+            vertical,
+            direction: -w,
+            shutter: Shutter::default(),
+        }
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        let origin = self
+            .horizontal
+            .mul_add(s, self.vertical.mul_add(t, self.lower_left_corner));
+
+        Some(Ray::new(origin, self.direction).with_time(self.shutter.sample()))
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec3::Length;
+
+    #[test]
+    fn test_get_ray() {
+        // Film 4 units wide and 2 high, at z = 5 looking down -z
+        let camera = OrthographicCamera::new(
+            Point3::new(0.0, 0.0, 5.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            2.0,
+            2.0,
+        );
+        let forward = Vec3::new(0.0, 0.0, -1.0);
+        for (s, t, origin) in [
+            (0.5, 0.5, Point3::new(0.0, 0.0, 5.0)),
+            (1.0, 0.5, Point3::new(2.0, 0.0, 5.0)),
+            (0.0, 0.0, Point3::new(-2.0, -1.0, 5.0)),
+        ] {
+            let r = camera.get_ray(s, t).unwrap();
+            assert!((r.origin() - origin).length() < 1e-12, "{:?}", r.origin());
+            assert!((r.direction() - forward).length() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::point3::Point3;
+use crate::vec3::{Dot, RandomRanged, Unit, Vec3};
+
+const POINT_COUNT: usize = 256;
+
+// Perlin gradient noise
+//
+// Random unit gradients on the integer lattice, hashed by three permutation tables and blended
+// with a Hermite smoothed trilinear interpolation. noise() is in [-1, 1].
+pub struct Perlin {
+    gradients: Vec<Vec3>,
+    perm_x: Vec<usize>,
+    perm_y: Vec<usize>,
+    perm_z: Vec<usize>,
+}
+
+impl Perlin {
+    pub fn new() -> Self {
+        let gradients = (0..POINT_COUNT)
+            .map(|_| Vec3::random_ranged(&(-1.0..1.0)).unit())
+            .collect();
+
+        Self {
+            gradients,
+            perm_x: Self::generate_perm(),
+            perm_y: Self::generate_perm(),
+            perm_z: Self::generate_perm(),
+        }
+    }
+
+    fn generate_perm() -> Vec<usize> {
+        let mut perm: Vec<usize> = (0..POINT_COUNT).collect();
+        fastrand::shuffle(&mut perm);
+        perm
+    }
+
+    pub fn noise(&self, p: &Point3) -> f64 {
+        let (i, u) = (p.x().floor(), p.x() - p.x().floor());
+        let (j, v) = (p.y().floor(), p.y() - p.y().floor());
+        let (k, w) = (p.z().floor(), p.z() - p.z().floor());
+
+        let mut accum = 0.0;
+        for di in 0..2 {
+            for dj in 0..2 {
+                for dk in 0..2 {
+                    let hash = self.perm_x[(i as i64 + di) as usize & (POINT_COUNT - 1)]
+                        ^ self.perm_y[(j as i64 + dj) as usize & (POINT_COUNT - 1)]
+                        ^ self.perm_z[(k as i64 + dk) as usize & (POINT_COUNT - 1)];
+
+                    // Hermite cubic smoothing of the blending weights
+                    let weight = |d: i64, t: f64| {
+                        let t = t * t * (3.0 - 2.0 * t);
+                        match d {
+                            0 => 1.0 - t,
+                            _ => t,
+                        }
+                    };
+                    let offset = Vec3::new(u - di as f64, v - dj as f64, w - dk as f64);
+                    accum += weight(di, u)
+                        * weight(dj, v)
+                        * weight(dk, w)
+                        * self.gradients[hash].dot(offset);
+                }
+            }
+        }
+
+        accum
+    }
+
+    // Sum of noise octaves of halving amplitude and doubling frequency, in [0, 2).
+    pub fn turbulence(&self, p: &Point3, depth: usize) -> f64 {
+        let mut accum = 0.0;
+        let mut p = *p;
+        let mut weight = 1.0;
+        for _ in 0..depth {
+            accum += weight * self.noise(&p);
+            weight *= 0.5;
+            p = 2.0 * p;
+        }
+
+        accum.abs()
+    }
+}
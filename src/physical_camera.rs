@@ -0,0 +1,68 @@
+// Photographic description of a camera
+//
+// Lengths of the lens and the sensor are in millimeters, as printed on them. The scene is
+// measured in scene_scale units per meter, and its radiance is taken as luminance in cd/m^2, so
+// photographic exposure settings give the expected brightness.
+#[derive(Clone)]
+pub struct PhysicalParameters {
+    pub focal_length: f64,  // mm
+    pub sensor_width: f64,  // mm, fit to the width of the image
+    pub f_stop: f64,        // Focal length / aperture diameter
+    pub shutter_speed: f64, // Seconds the shutter stays open
+    pub iso: f64,           // Sensitivity
+    pub scene_scale: f64,   // Scene units per meter
+}
+
+impl Default for PhysicalParameters {
+    // Standard lens on a full frame sensor, at the "sunny 16" exposure
+    fn default() -> Self {
+        Self {
+            focal_length: 50.0,
+            sensor_width: 36.0,
+            f_stop: 16.0,
+            shutter_speed: 1.0 / 100.0,
+            iso: 100.0,
+            scene_scale: 1.0,
+        }
+    }
+}
+
+impl PhysicalParameters {
+    // Vertical field of view in degrees, for an image of the given aspect ratio.
+    pub fn vertical_fov(&self, aspect_ratio: f64) -> f64 {
+        let sensor_height = self.sensor_width / aspect_ratio;
+        2.0 * (sensor_height / (2.0 * self.focal_length))
+            .atan()
+            .to_degrees()
+    }
+
+    // Diameter of the aperture, in scene units.
+    pub fn aperture(&self) -> f64 {
+        self.focal_length / self.f_stop / 1000.0 * self.scene_scale
+    }
+
+    // Scale from luminance to pixel values, with the saturation based sensitivity of ISO 12232:
+    // the luminance saturating the sensor is 78 / (S * q) * N^2 / t, with q = 0.65.
+    pub fn exposure(&self) -> f64 {
+        let saturation = 78.0 / (self.iso * 0.65) * self.f_stop * self.f_stop / self.shutter_speed;
+        1.0 / saturation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived() {
+        let parameters = PhysicalParameters::default();
+
+        // A 50 mm lens on a full frame sensor sees about 27 degrees vertically in 3:2
+        assert!((parameters.vertical_fov(1.5) - 26.99).abs() < 0.01);
+        assert!((parameters.aperture() - 0.003125).abs() < 1e-12);
+
+        // Sunny 16 saturates with about 30000 cd/m^2, sunlit white
+        let exposure = parameters.exposure();
+        assert!((30720.0 * exposure - 1.0).abs() < 1e-9, "{exposure}");
+    }
+}
@@ -0,0 +1,97 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::onb::Onb;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Unit, Vec3};
+
+// Infinite plane through point, facing normal.
+pub struct Plane {
+    point: Point3,
+    normal: Vec3,
+    onb: Onb, // Tangent frame for the UVs
+    material: Box<dyn Material>,
+}
+
+impl Plane {
+    pub fn new(point: Point3, normal: Vec3, material: Box<dyn Material>) -> Self {
+        let normal = normal.unit();
+        let onb = Onb::new(&normal);
+        Self {
+            point,
+            normal,
+            onb,
+            material,
+        }
+    }
+}
+
+impl Hittable for Plane {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Points in the plane P: (P - point) · normal = 0
+        //
+        // with P being a ray, P(t) = A + B*t:
+        //   t = (point - A) · normal / (B · normal)
+        let denominator = r.direction().dot(self.normal);
+        if denominator.abs() < 1e-8 {
+            // Parallel to the plane
+            return None;
+        }
+
+        let t = (self.point - r.origin()).dot(self.normal) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        // UVs are the plane coordinates, in scene units
+        let p = r.at(t);
+        let local = self.onb.to_local(&(p - self.point));
+        let hit_record =
+            HitRecord::new(p, self.normal, &*self.material, t, r).with_uv(local.x(), local.y());
+
+        Some(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+    use crate::vec3::Length;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let plane = Plane::new(
+            Point3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            material,
+        );
+        let t_range = 0.001..f64::INFINITY;
+
+        // From above, far from the point, the UVs are the distance to it within the plane
+        let r = Ray::new(Point3::new(3.0, 5.0, 4.0), Vec3::new(0.0, -2.0, 0.0));
+        let hit_record = plane.hit(&r, &t_range).unwrap();
+        assert!((hit_record.t() - 2.0).abs() < 1e-12);
+        assert!((hit_record.p() - Point3::new(3.0, 1.0, 4.0)).length() < 1e-12);
+        assert_eq!(Vec3::new(0.0, 1.0, 0.0), *hit_record.normal());
+        let (u, v) = hit_record.uv();
+        assert!(((u * u + v * v).sqrt() - 5.0).abs() < 1e-12);
+
+        // From below
+        let r = Ray::new(Point3::new(0.0, -1.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+        let hit_record = plane.hit(&r, &t_range).unwrap();
+        assert!(!hit_record.front_face());
+        assert_eq!(Vec3::new(0.0, -1.0, 0.0), *hit_record.normal());
+
+        // Parallel, and going away
+        let r = Ray::new(Point3::new(0.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 1.0));
+        assert!(plane.hit(&r, &t_range).is_none());
+        let r = Ray::new(Point3::new(0.0, 2.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(plane.hit(&r, &t_range).is_none());
+    }
+}
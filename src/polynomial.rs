@@ -0,0 +1,181 @@
+// Real roots of polynomials up to degree four
+//
+// Closed form solutions from Schwarze, "Cubic and Quartic Roots", Graphics Gems, 1990. The
+// coefficients are given from the constant term up: c[0] + c[1]*x + c[2]*x^2 + ...
+
+const EPSILON: f64 = 1e-9;
+
+fn is_zero(x: f64) -> bool {
+    x.abs() < EPSILON
+}
+
+// c[0] + c[1]*x + c[2]*x^2 = 0
+pub fn solve_quadratic(c: &[f64; 3]) -> Vec<f64> {
+    // Normal form: x^2 + 2*p*x + q = 0
+    let p = c[1] / (2.0 * c[2]);
+    let q = c[0] / c[2];
+
+    let discriminant = p * p - q;
+    if is_zero(discriminant) {
+        vec![-p]
+    } else if discriminant < 0.0 {
+        vec![]
+    } else {
+        let sqrt_d = discriminant.sqrt();
+        vec![sqrt_d - p, -sqrt_d - p]
+    }
+}
+
+// c[0] + c[1]*x + c[2]*x^2 + c[3]*x^3 = 0
+pub fn solve_cubic(c: &[f64; 4]) -> Vec<f64> {
+    // Normal form: x^3 + A*x^2 + B*x + C = 0
+    let a = c[2] / c[3];
+    let b = c[1] / c[3];
+    let c = c[0] / c[3];
+
+    // Substitute x = y - A/3 to eliminate the quadric term: y^3 + 3*p*y + 2*q = 0
+    let sq_a = a * a;
+    let p = (-sq_a / 3.0 + b) / 3.0;
+    let q = (2.0 / 27.0 * a * sq_a - a * b / 3.0 + c) / 2.0;
+
+    // Cardano's formula
+    let cb_p = p * p * p;
+    let discriminant = q * q + cb_p;
+
+    let roots = if is_zero(discriminant) {
+        if is_zero(q) {
+            // One triple solution
+            vec![0.0]
+        } else {
+            // One single and one double solution
+            let u = (-q).cbrt();
+            vec![2.0 * u, -u]
+        }
+    } else if discriminant < 0.0 {
+        // Casus irreducibilis: three real solutions
+        let phi = (-q / (-cb_p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        let t = 2.0 * (-p).sqrt();
+        vec![
+            t * phi.cos(),
+            -t * (phi + std::f64::consts::PI / 3.0).cos(),
+            -t * (phi - std::f64::consts::PI / 3.0).cos(),
+        ]
+    } else {
+        // One real solution
+        let sqrt_d = discriminant.sqrt();
+        let u = (sqrt_d - q).cbrt();
+        let v = -(sqrt_d + q).cbrt();
+        vec![u + v]
+    };
+
+    // Resubstitute
+    roots.into_iter().map(|y| y - a / 3.0).collect()
+}
+
+// c[0] + c[1]*x + c[2]*x^2 + c[3]*x^3 + c[4]*x^4 = 0
+pub fn solve_quartic(c: &[f64; 5]) -> Vec<f64> {
+    // Normal form: x^4 + A*x^3 + B*x^2 + C*x + D = 0
+    let a = c[3] / c[4];
+    let b = c[2] / c[4];
+    let cc = c[1] / c[4];
+    let d = c[0] / c[4];
+
+    // Substitute x = y - A/4 to eliminate the cubic term: y^4 + p*y^2 + q*y + r = 0
+    let sq_a = a * a;
+    let p = -3.0 / 8.0 * sq_a + b;
+    let q = sq_a * a / 8.0 - a * b / 2.0 + cc;
+    let r = -3.0 / 256.0 * sq_a * sq_a + sq_a * b / 16.0 - a * cc / 4.0 + d;
+
+    let roots = if is_zero(r) {
+        // No absolute term: y * (y^3 + p*y + q) = 0
+        let mut roots = solve_cubic(&[q, p, 0.0, 1.0]);
+        roots.push(0.0);
+        roots
+    } else {
+        // Solve the resolvent cubic and take one real solution
+        let z = solve_cubic(&[r * p / 2.0 - q * q / 8.0, -r, -p / 2.0, 1.0])[0];
+
+        // ... to build two quadric equations
+        let u = z * z - r;
+        let v = 2.0 * z - p;
+        let u = match is_zero(u) {
+            true => 0.0,
+            false if u > 0.0 => u.sqrt(),
+            false => return vec![],
+        };
+        let v = match is_zero(v) {
+            true => 0.0,
+            false if v > 0.0 => v.sqrt(),
+            false => return vec![],
+        };
+        let v = match q < 0.0 {
+            true => -v,
+            false => v,
+        };
+
+        let mut roots = solve_quadratic(&[z - u, v, 1.0]);
+        roots.extend(solve_quadratic(&[z + u, -v, 1.0]));
+        roots
+    };
+
+    // Resubstitute, and polish the roots with Newton's method as the closed form loses precision
+    roots
+        .into_iter()
+        .map(|y| y - a / 4.0)
+        .map(|x| {
+            let mut x = x;
+            for _ in 0..2 {
+                let f = (((c[4] * x + c[3]) * x + c[2]) * x + c[1]) * x + c[0];
+                let df = ((4.0 * c[4] * x + 3.0 * c[3]) * x + 2.0 * c[2]) * x + c[1];
+                if df == 0.0 {
+                    break;
+                }
+                x -= f / df;
+            }
+            x
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roots(mut roots: Vec<f64>, expected: &[f64]) {
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        roots.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+        assert_eq!(roots.len(), expected.len(), "{roots:?} != {expected:?}");
+        for (root, expected) in roots.iter().zip(expected.iter()) {
+            assert!((root - expected).abs() < 1e-6, "{roots:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_solve_quadratic() {
+        // (x - 1) * (x + 3)
+        assert_roots(solve_quadratic(&[-3.0, 2.0, 1.0]), &[-3.0, 1.0]);
+        // x^2 + 1
+        assert_roots(solve_quadratic(&[1.0, 0.0, 1.0]), &[]);
+    }
+
+    #[test]
+    fn test_solve_cubic() {
+        // (x - 1) * (x - 2) * (x - 3)
+        assert_roots(solve_cubic(&[-6.0, 11.0, -6.0, 1.0]), &[1.0, 2.0, 3.0]);
+        // (x - 2) * (x^2 + 1)
+        assert_roots(solve_cubic(&[-2.0, 1.0, -2.0, 1.0]), &[2.0]);
+    }
+
+    #[test]
+    fn test_solve_quartic() {
+        // (x - 1) * (x - 2) * (x - 3) * (x - 4)
+        assert_roots(
+            solve_quartic(&[24.0, -50.0, 35.0, -10.0, 1.0]),
+            &[1.0, 2.0, 3.0, 4.0],
+        );
+        // 2 * (x + 1) * (x - 0.5) * (x^2 + 1)
+        assert_roots(solve_quartic(&[-1.0, 1.0, 1.0, 1.0, 2.0]), &[-1.0, 0.5]);
+        // x^4 + 1
+        assert_roots(solve_quartic(&[1.0, 0.0, 0.0, 0.0, 1.0]), &[]);
+    }
+}
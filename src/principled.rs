@@ -0,0 +1,385 @@
+use std::f64::consts::PI;
+
+use crate::color::{Color, WHITE};
+use crate::fresnel::fr_dielectric;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::microfacet::TrowbridgeReitz;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::rough_dielectric::RoughDielectric;
+use crate::vec3::{Dot, Reflect, Unit, Vec3};
+
+// Parameters of the principled material.
+//
+// They match the metallic-roughness model of glTF 2.0 (plus its clearcoat, sheen, specular,
+// transmission and ior extensions) and Blender's Principled BSDF, so asset importers can map
+// exported materials one to one. The defaults are the ones of a new Blender material.
+#[derive(Clone)]
+pub struct PrincipledParameters {
+    pub base_color: Color,
+    pub metallic: f64,
+    pub roughness: f64,
+    pub specular: f64, // 0.5 is the physically correct reflectance for the given ior
+    pub clearcoat: f64,
+    pub clearcoat_roughness: f64,
+    pub sheen: f64,
+    pub sheen_tint: f64,
+    pub transmission: f64,
+    pub ior: f64,
+}
+
+impl Default for PrincipledParameters {
+    fn default() -> Self {
+        Self {
+            base_color: Color::new(0.8, 0.8, 0.8),
+            metallic: 0.0,
+            roughness: 0.5,
+            specular: 0.5,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.03,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            transmission: 0.0,
+            ior: 1.45,
+        }
+    }
+}
+
+// Layers of the principled material, one of which scatters each ray
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Lobe {
+    Clearcoat,
+    Transmission,
+    Metallic,
+    Dielectric,
+}
+
+// Principled (Disney-style) uber material
+//
+// A stack of lobes that are picked stochastically at each hit, so every lobe is importance
+// sampled on its own:
+//   - A clearcoat layer, a GGX reflection with a fixed ior of 1.5.
+//   - A metallic base, a GGX reflection with Schlick's Fresnel tinted by the base color.
+//   - A transmissive base, a rough dielectric tinted by the base color.
+//   - A dielectric base, a GGX specular reflection over a diffuse lobe with sheen.
+pub struct Principled {
+    parameters: PrincipledParameters,
+    distribution: TrowbridgeReitz,
+    clearcoat_distribution: TrowbridgeReitz,
+    transmission: RoughDielectric,
+}
+
+impl Principled {
+    const CLEARCOAT_IOR: f64 = 1.5;
+
+    pub fn new(parameters: PrincipledParameters) -> Self {
+        let distribution = TrowbridgeReitz::new(parameters.roughness);
+        let clearcoat_distribution = TrowbridgeReitz::new(parameters.clearcoat_roughness);
+        let transmission = RoughDielectric::new(parameters.ior, parameters.roughness);
+
+        Self {
+            parameters,
+            distribution,
+            clearcoat_distribution,
+            transmission,
+        }
+    }
+
+    // Core of the metallic-roughness model, what every exporter writes. Importers map the glTF
+    // baseColorFactor, metallicFactor, roughnessFactor, and the KHR_materials_ior and
+    // KHR_materials_transmission extensions (or the same inputs of Blender's Principled BSDF)
+    // here, then set the other parameters of the asset, if any, with new().
+    pub fn metallic_roughness(
+        base_color: Color,
+        metallic: f64,
+        roughness: f64,
+        ior: f64,
+        transmission: f64,
+    ) -> anyhow::Result<Self> {
+        let unit = 0.0..=1.0;
+        let channels = [base_color.x(), base_color.y(), base_color.z()];
+        if !channels.iter().all(|channel| unit.contains(channel)) {
+            anyhow::bail!("base color {base_color:?} outside of [0, 1]");
+        }
+        for (name, value) in [
+            ("metallic", metallic),
+            ("roughness", roughness),
+            ("transmission", transmission),
+        ] {
+            if !unit.contains(&value) {
+                anyhow::bail!("{name} {value} outside of [0, 1]");
+            }
+        }
+        if ior.is_nan() || ior < 1.0 {
+            anyhow::bail!("index of refraction {ior} below 1");
+        }
+
+        Ok(Self::new(PrincipledParameters {
+            base_color,
+            metallic,
+            roughness,
+            ior,
+            transmission,
+            ..Default::default()
+        }))
+    }
+
+    // Share of the base that transmits, the metallic part is opaque
+    fn transmissive(&self) -> f64 {
+        (1.0 - self.parameters.metallic) * self.parameters.transmission
+    }
+
+    // Pick the lobe that scatters wo: the clearcoat reflects its Fresnel share of the light, and
+    // the base is split between the transmissive, metallic and dielectric lobes by their weights.
+    fn lobe(&self, wo: &Vec3) -> Lobe {
+        let clearcoat = self.parameters.clearcoat * fr_dielectric(wo.z(), Self::CLEARCOAT_IOR);
+        if clearcoat > fastrand::f64() {
+            return Lobe::Clearcoat;
+        }
+
+        let base = fastrand::f64();
+        let transmissive = self.transmissive();
+        if base < transmissive {
+            Lobe::Transmission
+        } else if base < transmissive + self.parameters.metallic {
+            Lobe::Metallic
+        } else {
+            Lobe::Dielectric
+        }
+    }
+
+    // GGX reflection of wo, with the throughput weight G / G1 of the visible normal sampling.
+    fn sample_specular(distribution: &TrowbridgeReitz, wo: &Vec3) -> Option<(Vec3, Vec3, f64)> {
+        let wm = match distribution.effectively_smooth() {
+            true => Vec3::new(0.0, 0.0, 1.0),
+            false => distribution.sample_wm(wo),
+        };
+        let wi = (-wo).reflect(wm);
+        if wi.z() <= 0.0 {
+            return None;
+        }
+        let masking = match distribution.effectively_smooth() {
+            true => 1.0,
+            false => distribution.g(wo, &wi) / distribution.g1(wo),
+        };
+
+        Some((wi, wm, masking))
+    }
+
+    fn metallic(&self, wo: &Vec3) -> Option<(Vec3, Color)> {
+        let (wi, wm, masking) = Self::sample_specular(&self.distribution, wo)?;
+        let fresnel = schlick(&self.parameters.base_color, wo.dot(wm));
+
+        Some((wi, masking * fresnel))
+    }
+
+    fn dielectric(&self, wo: &Vec3) -> Option<(Vec3, Color)> {
+        // The specular layer reflects according to Fresnel, the rest reaches the diffuse base.
+        let (wi, wm, masking) = Self::sample_specular(&self.distribution, wo)?;
+        let reflectance =
+            2.0 * self.parameters.specular * fr_dielectric(wo.dot(wm), self.parameters.ior);
+        if reflectance.min(1.0) > fastrand::f64() {
+            return Some((wi, masking * WHITE));
+        }
+
+        let wi = random_cosine_direction();
+        let attenuation = self.parameters.base_color + self.sheen(wo, &wi);
+
+        Some((wi, attenuation))
+    }
+
+    // Grazing retro-reflection for cloth-like materials. The diffuse lobe is cosine sampled, so
+    // the throughput weight f * cos / pdf is f * PI.
+    fn sheen(&self, wo: &Vec3, wi: &Vec3) -> Color {
+        if self.parameters.sheen == 0.0 {
+            return Color::default();
+        }
+
+        let base_color = &self.parameters.base_color;
+        let luminance = 0.2126 * base_color.x() + 0.7152 * base_color.y() + 0.0722 * base_color.z();
+        let tint = match luminance > 0.0 {
+            true => base_color / luminance,
+            false => WHITE,
+        };
+        let sheen_color = WHITE + self.parameters.sheen_tint * (tint - WHITE);
+
+        // f = sheen * sheen_color * (1 - cos(theta_d))^5 / PI
+        let cos_theta_d = wi.dot((wo + wi).unit());
+        let weight = self.parameters.sheen * (1.0 - cos_theta_d).powi(5);
+
+        weight * sheen_color
+    }
+}
+
+impl Material for Principled {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        // Rays only get inside the object by transmission, and the only way out is through it.
+        if self.transmissive() > 0.0 && !hit_record.front_face() {
+            return self.transmission.scatter(r_in, hit_record);
+        }
+
+        let onb = Onb::new(hit_record.normal());
+        let wo = onb.to_local(&-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let (wi, attenuation) = match self.lobe(&wo) {
+            Lobe::Clearcoat => {
+                let (wi, _, masking) = Self::sample_specular(&self.clearcoat_distribution, &wo)?;
+                (wi, masking * WHITE)
+            }
+            Lobe::Transmission => {
+                let (scattered, attenuation) = self.transmission.scatter(r_in, hit_record)?;
+                return Some((scattered, self.parameters.base_color * attenuation));
+            }
+            Lobe::Metallic => self.metallic(&wo)?,
+            Lobe::Dielectric => self.dielectric(&wo)?,
+        };
+        let scattered = r_in.spawn(hit_record.p(), onb.to_world(&wi));
+
+        Some((scattered, attenuation))
+    }
+}
+
+// Schlick's approximation of Fresnel, for a given reflectance at normal incidence.
+fn schlick(f0: &Color, cos_theta: f64) -> Color {
+    let weight = (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5);
+    f0 + weight * (WHITE - f0)
+}
+
+// Cosine distributed direction in the local shading frame.
+fn random_cosine_direction() -> Vec3 {
+    let r1 = fastrand::f64();
+    let r2 = fastrand::f64();
+
+    let phi = 2.0 * PI * r1;
+    let x = phi.cos() * r2.sqrt();
+    let y = phi.sin() * r2.sqrt();
+    let z = (1.0 - r2).sqrt();
+
+    Vec3::new(x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point3::Point3;
+
+    const SAMPLES: usize = 20000;
+
+    // Average attenuation of the light scattered by material, for a ray hitting the y = 0 plane
+    // at the origin with angle theta to the normal, in degrees.
+    fn albedo(material: &Principled, theta: f64) -> Color {
+        let theta = theta.to_radians();
+        let direction = Vec3::new(theta.sin(), -theta.cos(), 0.0);
+        let r_in = Ray::new(Point3::new(0.0, 0.0, 0.0) - direction, direction);
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let hit_record = HitRecord::new(Point3::new(0.0, 0.0, 0.0), normal, material, 1.0, &r_in);
+        let total = (0..SAMPLES)
+            .filter_map(|_| material.scatter(&r_in, &hit_record))
+            .fold(Color::default(), |total, (_, attenuation)| {
+                total + attenuation
+            });
+
+        total / SAMPLES as f64
+    }
+
+    #[test]
+    fn test_lobes() {
+        let frequency = |material: &Principled, lobe: Lobe| {
+            let wo = Vec3::new(0.0, 0.0, 1.0);
+            let count = (0..SAMPLES).filter(|_| material.lobe(&wo) == lobe).count();
+            count as f64 / SAMPLES as f64
+        };
+
+        let material = Principled::new(PrincipledParameters {
+            metallic: 0.5,
+            transmission: 0.5,
+            ..Default::default()
+        });
+        assert_eq!(0.0, frequency(&material, Lobe::Clearcoat));
+        assert!((frequency(&material, Lobe::Transmission) - 0.25).abs() < 0.02);
+        assert!((frequency(&material, Lobe::Metallic) - 0.5).abs() < 0.02);
+        assert!((frequency(&material, Lobe::Dielectric) - 0.25).abs() < 0.02);
+
+        // The clearcoat takes its Fresnel reflectance, 4% at normal incidence
+        let material = Principled::new(PrincipledParameters {
+            clearcoat: 1.0,
+            ..Default::default()
+        });
+        assert!((frequency(&material, Lobe::Clearcoat) - 0.04).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_scattering_pdf() {
+        // The lobes can't be described by a single density, so the lights are never sampled for
+        // the material, which keeps its weights unbiased.
+        let material = Principled::new(PrincipledParameters::default());
+        let r_in = Ray::new(Point3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let normal = Vec3::new(0.0, 1.0, 0.0);
+        let hit_record = HitRecord::new(Point3::new(0.0, 0.0, 0.0), normal, &material, 1.0, &r_in);
+        for (scattered, _) in (0..100).filter_map(|_| material.scatter(&r_in, &hit_record)) {
+            assert_eq!(0.0, material.scattering_pdf(&r_in, &hit_record, &scattered));
+        }
+    }
+
+    #[test]
+    fn test_energy() {
+        let white = Color::new(1.0, 1.0, 1.0);
+        let materials = [
+            PrincipledParameters::default(),
+            PrincipledParameters {
+                base_color: white,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: white,
+                metallic: 1.0,
+                roughness: 0.6,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: white,
+                roughness: 0.2,
+                specular: 1.0,
+                clearcoat: 1.0,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: white,
+                metallic: 0.3,
+                roughness: 0.3,
+                transmission: 1.0,
+                ior: 1.5,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: Color::new(0.8, 0.8, 0.8),
+                roughness: 1.0,
+                sheen: 1.0,
+                ..Default::default()
+            },
+        ];
+        for parameters in materials {
+            let material = Principled::new(parameters);
+            for theta in [0.0, 45.0, 80.0] {
+                let albedo = albedo(&material, theta);
+                let max = albedo.x().max(albedo.y()).max(albedo.z());
+                assert!(max <= 1.02, "{max} at {theta} degrees");
+            }
+        }
+    }
+
+    #[test]
+    fn test_metallic_roughness() {
+        let orange = Color::new(0.9, 0.4, 0.1);
+        assert!(Principled::metallic_roughness(orange, 1.0, 0.3, 1.5, 0.0).is_ok());
+        assert!(Principled::metallic_roughness(2.0 * orange, 1.0, 0.3, 1.5, 0.0).is_err());
+        assert!(Principled::metallic_roughness(orange, 1.5, 0.3, 1.5, 0.0).is_err());
+        assert!(Principled::metallic_roughness(orange, 1.0, -0.1, 1.5, 0.0).is_err());
+        assert!(Principled::metallic_roughness(orange, 1.0, 0.3, 0.5, 0.0).is_err());
+        assert!(Principled::metallic_roughness(orange, 1.0, 0.3, 1.5, f64::NAN).is_err());
+    }
+}
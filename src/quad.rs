@@ -0,0 +1,152 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Cross, Dot, Length, LengthSquared, MulAdd, Unit, Vec3};
+
+// Parallelogram with a corner in q and sides u and v.
+pub struct Quad {
+    q: Point3,
+    u: Vec3,
+    v: Vec3,
+    normal: Vec3,
+    w: Vec3, // n / (n · n), to get the planar coordinates of a point
+    area: f64,
+    material: Box<dyn Material>,
+}
+
+impl Quad {
+    pub fn new(q: Point3, u: Vec3, v: Vec3, material: Box<dyn Material>) -> Self {
+        let n = u.cross(v);
+        let normal = n.unit();
+        let w = n / n.length_squared();
+        let area = n.length();
+
+        Self {
+            q,
+            u,
+            v,
+            normal,
+            w,
+            area,
+            material,
+        }
+    }
+
+    // Planar coordinates (alpha, beta) of the ray hit: P = q + alpha * u + beta * v
+    fn hit_plane(&self, r: &Ray, t_range: &Range<f64>) -> Option<(f64, f64, f64)> {
+        let denominator = r.direction().dot(self.normal);
+        if denominator.abs() < 1e-8 {
+            // Parallel to the plane
+            return None;
+        }
+
+        let t = (self.q - r.origin()).dot(self.normal) / denominator;
+        if !t_range.contains(&t) {
+            return None;
+        }
+
+        let planar_hit = r.at(t) - self.q;
+        let alpha = self.w.dot(planar_hit.cross(self.v));
+        let beta = self.w.dot(self.u.cross(planar_hit));
+
+        Some((t, alpha, beta))
+    }
+}
+
+impl Hittable for Quad {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let (t, alpha, beta) = self.hit_plane(r, t_range)?;
+
+        const UNIT: Range<f64> = 0.0..1.0;
+        if !UNIT.contains(&alpha) || !UNIT.contains(&beta) {
+            return None;
+        }
+
+        let p = r.at(t);
+        let hit_record = HitRecord::new(p, self.normal, &*self.material, t, r).with_uv(alpha, beta);
+
+        Some(hit_record)
+    }
+
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        let r = Ray::new(*origin, *direction);
+        let Some(hit_record) = self.hit(&r, &(0.001..f64::INFINITY)) else {
+            return 0.0;
+        };
+
+        // Convert the uniform area density to solid angle
+        let distance_squared = hit_record.t() * hit_record.t() * direction.length_squared();
+        let cosine = (direction.dot(self.normal) / direction.length()).abs();
+
+        distance_squared / (cosine * self.area)
+    }
+
+    fn random(&self, origin: &Point3) -> Option<Vec3> {
+        let p = self
+            .u
+            .mul_add(fastrand::f64(), self.v.mul_add(fastrand::f64(), self.q));
+
+        Some(p - origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::hittable::tests::check_sampling;
+    use crate::lambertian::Lambertian;
+
+    // Unit square at z = -1, facing the origin
+    fn quad() -> Quad {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        Quad::new(
+            Point3::new(-0.5, -0.5, -1.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material,
+        )
+    }
+
+    #[test]
+    fn test_hit() {
+        let quad = quad();
+        let origin = Point3::new(0.0, 0.0, 0.0);
+        let t_range = 0.001..f64::INFINITY;
+
+        let r = Ray::new(origin, Vec3::new(0.25, 0.25, -1.0));
+        let hit_record = quad.hit(&r, &t_range).unwrap();
+        assert!((hit_record.t() - 1.0).abs() < 1e-12);
+        assert_eq!((0.75, 0.75), hit_record.uv());
+        assert_eq!(Vec3::new(0.0, 0.0, 1.0), *hit_record.normal());
+        assert!(hit_record.front_face());
+
+        // Past its side, parallel to it and behind the ray
+        assert!(quad
+            .hit(&Ray::new(origin, Vec3::new(0.6, 0.0, -1.0)), &t_range)
+            .is_none());
+        assert!(quad
+            .hit(&Ray::new(origin, Vec3::new(1.0, 0.0, 0.0)), &t_range)
+            .is_none());
+        assert!(quad
+            .hit(&Ray::new(origin, Vec3::new(0.0, 0.0, 1.0)), &t_range)
+            .is_none());
+    }
+
+    #[test]
+    fn test_sampling() {
+        let quad = quad();
+        check_sampling(&quad, &Point3::new(0.0, 0.0, 0.0));
+        check_sampling(&quad, &Point3::new(0.3, -0.2, -1.5));
+
+        // Area density converted to solid angle, straight in front of its center
+        let pdf = quad.pdf_value(&Point3::new(0.0, 0.0, 0.0), &Vec3::new(0.0, 0.0, -1.0));
+        assert!((pdf - 1.0).abs() < 1e-12);
+        let pdf = quad.pdf_value(&Point3::new(0.0, 0.0, 0.0), &Vec3::new(0.0, 1.0, -1.0));
+        assert_eq!(0.0, pdf);
+    }
+}
@@ -1,15 +1,44 @@
 use crate::point3::Point3;
+use crate::spectrum::SampledWavelengths;
 use crate::vec3::{MulAdd, Vec3};
 
 #[derive(Default)]
 pub struct Ray {
     origin: Point3,
     direction: Vec3,
+    wavelengths: Option<SampledWavelengths>, // Only in spectral mode
+    time: f64,                               // Instant within the camera shutter interval
 }
 
 impl Ray {
     pub fn new(origin: Point3, direction: Vec3) -> Self {
-        Self { origin, direction }
+        Self {
+            origin,
+            direction,
+            wavelengths: None,
+            time: 0.0,
+        }
+    }
+
+    pub fn with_time(self, time: f64) -> Self {
+        Self { time, ..self }
+    }
+
+    pub fn with_wavelengths(self, wavelengths: SampledWavelengths) -> Self {
+        Self {
+            wavelengths: Some(wavelengths),
+            ..self
+        }
+    }
+
+    // New ray of the same path, e.g. the ray scattered by a material. It keeps the path state.
+    pub fn spawn(&self, origin: Point3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction,
+            wavelengths: self.wavelengths,
+            time: self.time,
+        }
     }
 
     pub fn origin(&self) -> &Point3 {
@@ -20,6 +49,20 @@ impl Ray {
         &self.direction
     }
 
+    pub fn wavelengths(&self) -> Option<&SampledWavelengths> {
+        self.wavelengths.as_ref()
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn terminate_secondary_wavelengths(&mut self) {
+        if let Some(wavelengths) = self.wavelengths.as_mut() {
+            wavelengths.terminate_secondary();
+        }
+    }
+
     pub fn at(&self, t: f64) -> Point3 {
         // r(t) = origin + direction * t
         // self.origin + t * self.direction
@@ -0,0 +1,348 @@
+use anyhow::{bail, Context};
+
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Dot, MulAdd, RandomUnitDisk, Unit, Vec3};
+
+// Spherical surface of a lens element, or the aperture stop when flat
+struct LensSurface {
+    radius: f64, // Center of curvature at the vertex - radius along the optical axis
+    thickness: f64,
+    ior: f64, // Of the medium from this surface to the next one, towards the film
+    aperture_radius: f64,
+}
+
+// Sequence of lens surfaces from a prescription file
+//
+// One surface per line, from the object side to the film side, in millimeters: radius of
+// curvature (positive when the center is towards the film, 0 for the aperture stop), thickness
+// to the next surface, index of refraction up to the next surface (0 or 1 for air) and clear
+// aperture diameter. Lines starting with # are comments.
+pub struct LensSystem {
+    surfaces: Vec<LensSurface>,
+}
+
+impl LensSystem {
+    pub fn parse(prescription: &str) -> anyhow::Result<Self> {
+        let mut surfaces = Vec::new();
+        for (number, line) in prescription.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let values = line
+                .split_whitespace()
+                .map(|value| value.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .with_context(|| format!("line {}: bad number", number + 1))?;
+            let [radius, thickness, ior, aperture] = values[..] else {
+                bail!(
+                    "line {}: expected 4 values, got {}",
+                    number + 1,
+                    values.len()
+                );
+            };
+            if aperture <= 0.0 || thickness < 0.0 || ior < 0.0 {
+                bail!("line {}: bad surface {line:?}", number + 1);
+            }
+
+            surfaces.push(LensSurface {
+                radius,
+                thickness,
+                ior: if ior == 0.0 { 1.0 } else { ior },
+                aperture_radius: aperture / 2.0,
+            });
+        }
+        if surfaces.is_empty() {
+            bail!("lens without surfaces");
+        }
+
+        Ok(Self { surfaces })
+    }
+
+    pub fn load(path: &str) -> anyhow::Result<Self> {
+        let prescription =
+            std::fs::read_to_string(path).with_context(|| format!("reading {path}"))?;
+        Self::parse(&prescription).with_context(|| format!("loading {path}"))
+    }
+
+    // Trace a ray from the film, at z = 0, through all the surfaces towards the scene, along +z.
+    // The last surface sits at film_distance. None if it's blocked or totally reflected.
+    fn trace(
+        &self,
+        film_distance: f64,
+        origin: &Point3,
+        direction: &Vec3,
+    ) -> Option<(Point3, Vec3)> {
+        let mut origin = *origin;
+        let mut direction = direction.unit();
+        let mut z = film_distance;
+        for (i, surface) in self.surfaces.iter().enumerate().rev() {
+            // Intersection with the surface, whose vertex is at z
+            let (t, normal) = match surface.radius {
+                0.0 => {
+                    let t = (z - origin.z()) / direction.z();
+                    (t, Vec3::new(0.0, 0.0, -1.0))
+                }
+                radius => {
+                    let center = Point3::new(0.0, 0.0, z - radius);
+                    let oc = origin - center;
+                    let half_b = oc.dot(direction);
+                    let c = oc.dot(oc) - radius * radius;
+                    let discriminant = half_b * half_b - c;
+                    if discriminant < 0.0 {
+                        return None;
+                    }
+                    // The vertex is the far side of the sphere when its center is towards the film
+                    let t = match radius > 0.0 {
+                        true => -half_b + discriminant.sqrt(),
+                        false => -half_b - discriminant.sqrt(),
+                    };
+                    let p = direction.mul_add(t, origin);
+                    (t, (p - center).unit())
+                }
+            };
+            if t <= 0.0 {
+                return None;
+            }
+            origin = direction.mul_add(t, origin);
+            if origin.x() * origin.x() + origin.y() * origin.y()
+                > surface.aperture_radius * surface.aperture_radius
+            {
+                return None;
+            }
+
+            // Refraction into the medium in front of the surface
+            if surface.radius != 0.0 {
+                let eta_t = match i {
+                    0 => 1.0,
+                    _ => self.surfaces[i - 1].ior,
+                };
+                let normal = match normal.dot(direction) > 0.0 {
+                    true => -normal,
+                    false => normal,
+                };
+                direction = refract(&direction, &normal, surface.ior / eta_t)?;
+            }
+
+            z += match i {
+                0 => 0.0,
+                _ => self.surfaces[i - 1].thickness,
+            };
+        }
+
+        Some((origin, direction))
+    }
+
+    // Distance from the film to the rear surface that focuses at focus_distance from the film.
+    //
+    // A paraxial ray from the center of the film must cross the axis again at the focus distance.
+    // That happens past the distance focusing at infinity, which is found first.
+    fn focus(&self, focus_distance: f64) -> anyhow::Result<f64> {
+        let rear = &self.surfaces[self.surfaces.len() - 1];
+        let height = 0.01 * rear.aperture_radius;
+        let paraxial = |film_distance: f64| {
+            let direction = Vec3::new(height, 0.0, film_distance);
+            self.trace(film_distance, &Point3::default(), &direction)
+        };
+        let bisect = |mut low: f64, mut high: f64, too_close: &dyn Fn(f64) -> bool| {
+            for _ in 0..100 {
+                let middle = (low + high) / 2.0;
+                match too_close(middle) {
+                    true => low = middle,
+                    false => high = middle,
+                }
+            }
+            (low + high) / 2.0
+        };
+
+        // At infinity focus the ray leaves parallel to the axis, before it diverges
+        const MAX_FILM_DISTANCE: f64 = 1000.0;
+        let diverges = |d: f64| paraxial(d).is_none_or(|(_, direction)| direction.x() > 0.0);
+        if diverges(MAX_FILM_DISTANCE) {
+            bail!("lens doesn't focus");
+        }
+        let infinity = bisect(0.0, MAX_FILM_DISTANCE, &diverges);
+
+        // Axis crossing of the exit ray, from the film
+        let crossing = |d: f64| match paraxial(d) {
+            Some((p, direction)) if direction.x() < 0.0 => {
+                p.z() - p.x() / direction.x() * direction.z()
+            }
+            _ => f64::INFINITY,
+        };
+        // The object comes closer as the film moves back, down to unit magnification, so step
+        // back from infinity until it's close enough and refine in between
+        const STEP: f64 = 1.0;
+        let mut far = infinity;
+        while crossing(far + STEP) > focus_distance {
+            far += STEP;
+            if far > MAX_FILM_DISTANCE {
+                bail!("lens can't focus at {focus_distance} mm");
+            }
+        }
+
+        Ok(bisect(far, far + STEP, &|d| crossing(d) > focus_distance))
+    }
+}
+
+// Snell's law, with the normal against the direction, and eta the ratio of the indices of
+// refraction from the incident side to the transmitted one. None on total internal reflection.
+fn refract(direction: &Vec3, normal: &Vec3, eta: f64) -> Option<Vec3> {
+    let cos_theta = -direction.dot(normal);
+    let sin2_theta_t = eta * eta * (1.0 - cos_theta * cos_theta);
+    if sin2_theta_t > 1.0 {
+        return None;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+
+    Some(eta * direction + (eta * cos_theta - cos_theta_t) * normal)
+}
+
+// Camera tracing rays through a real lens system
+//
+// Rays go from a point of the film to a point of the rear element, and through every surface
+// to the scene. Those the lens stops are lost, which darkens the corners (vignetting), and the
+// refractions bring distortion and aberrations. The film is moved back and forth to focus.
+// The lens is in millimeters and the scene in scene_scale units per meter, 1 by default.
+pub struct RealisticCamera {
+    lens: LensSystem,
+    film_distance: f64, // From the film to the rear surface, mm
+    film_width: f64,
+    film_height: f64,
+    origin: Point3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    focus_distance: f64, // From the film, in scene units
+    scene_scale: f64,
+    shutter: Shutter,
+}
+
+impl RealisticCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        lens: LensSystem,
+        film_diagonal: f64, // mm
+        aspect_ratio: f64,
+        focus_distance: f64,
+    ) -> anyhow::Result<Self> {
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+        let film_height = film_diagonal / (1.0 + aspect_ratio * aspect_ratio).sqrt();
+        let film_distance = lens.focus(focus_distance * 1000.0)?;
+
+        Ok(Self {
+            lens,
+            film_distance,
+            film_width: aspect_ratio * film_height,
+            film_height,
+            origin: look_from,
+            u,
+            v,
+            w,
+            focus_distance,
+            scene_scale: 1.0,
+            shutter: Shutter::default(),
+        })
+    }
+
+    // Refocus for a scene in scene_scale units per meter
+    pub fn with_scene_scale(self, scene_scale: f64) -> anyhow::Result<Self> {
+        let film_distance = self
+            .lens
+            .focus(self.focus_distance / scene_scale * 1000.0)?;
+
+        Ok(Self {
+            film_distance,
+            scene_scale,
+            ..self
+        })
+    }
+
+    // From lens space to the world
+    fn to_world(&self, a: &Vec3) -> Vec3 {
+        self.u
+            .mul_add(a.x(), self.v.mul_add(a.y(), -a.z() * self.w))
+    }
+}
+
+impl Camera for RealisticCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        // The image on the film is upside down
+        let film = Point3::new(
+            (0.5 - s) * self.film_width,
+            (0.5 - t) * self.film_height,
+            0.0,
+        );
+
+        // Towards a uniformly distributed point of the rear element
+        let rear = &self.lens.surfaces[self.lens.surfaces.len() - 1];
+        let disk = rear.aperture_radius * Vec3::random_unit_disk();
+        let direction = Vec3::new(disk.x(), disk.y(), self.film_distance) - film;
+
+        // Natural vignetting: the irradiance falls off with cos^4, kept in that proportion
+        let cos_theta = direction.unit().z();
+        if fastrand::f64() > cos_theta.powi(4) {
+            return None;
+        }
+
+        let (origin, direction) = self.lens.trace(self.film_distance, &film, &direction)?;
+        let origin = self
+            .to_world(&origin)
+            .mul_add(self.scene_scale / 1000.0, self.origin);
+        let ray = Ray::new(origin, self.to_world(&direction));
+
+        Some(ray.with_time(self.shutter.sample()))
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOUBLE_GAUSS: &str = include_str!("../lenses/dgauss50mm.dat");
+
+    #[test]
+    fn test_parse() {
+        let lens = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+        assert_eq!(11, lens.surfaces.len());
+        assert_eq!(8.55, lens.surfaces[5].aperture_radius);
+        assert_eq!(1.0, lens.surfaces[5].ior);
+
+        assert!(LensSystem::parse("# nothing").is_err());
+        assert!(LensSystem::parse("10 1 1.5").is_err());
+    }
+
+    #[test]
+    fn test_focus() {
+        let lens = LensSystem::parse(DOUBLE_GAUSS).unwrap();
+
+        // Focusing at 1 m, paraxial rays from the film center meet again 1 m away
+        let film_distance = lens.focus(1000.0).unwrap();
+        let direction = Vec3::new(0.05, 0.0, film_distance);
+        let (p, d) = lens
+            .trace(film_distance, &Point3::default(), &direction)
+            .unwrap();
+        let crossing = p.z() - p.x() / d.x() * d.z();
+        assert!((crossing - 1000.0).abs() < 1.0, "{crossing}");
+
+        // An on axis ray goes straight through
+        let direction = Vec3::new(0.0, 0.0, 1.0);
+        let (p, d) = lens
+            .trace(film_distance, &Point3::default(), &direction)
+            .unwrap();
+        assert_eq!((0.0, 0.0), (p.x(), p.y()));
+        assert!((d.z() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::color::{Color, BLACK, WHITE};
+use crate::dielectric::{absorption_from_tint, transmittance};
+use crate::fresnel::fr_dielectric;
+use crate::hit_record::HitRecord;
+use crate::material::Material;
+use crate::microfacet::TrowbridgeReitz;
+use crate::onb::Onb;
+use crate::ray::Ray;
+use crate::vec3::{refract, Dot, Reflect, Unit, Vec3};
+
+// Rough dielectric (frosted glass)
+//
+// Microfacet BRDF + BTDF with a GGX distribution and the exact dielectric Fresnel equations. At
+// each hit the microfacet normal is sampled from the visible normals and Fresnel decides between
+// reflection and refraction, which makes the throughput weight G / G1 for both lobes.
+#[derive(Clone)]
+pub struct RoughDielectric {
+    ir: f64,           // Index of Refraction
+    absorption: Color, // Absorption coefficient of the medium, per unit of distance
+    distribution: TrowbridgeReitz,
+}
+
+impl RoughDielectric {
+    const ATTENUATION: Color = WHITE;
+    pub fn new(ir: f64, roughness: f64) -> Self {
+        let distribution = TrowbridgeReitz::new(roughness);
+        Self {
+            ir,
+            absorption: BLACK,
+            distribution,
+        }
+    }
+
+    // Colored frosted glass, see Dielectric::tinted
+    pub fn tinted(ir: f64, roughness: f64, tint: Color, distance: f64) -> anyhow::Result<Self> {
+        let absorption = absorption_from_tint(&tint, distance)?;
+        Ok(Self {
+            absorption,
+            ..Self::new(ir, roughness)
+        })
+    }
+}
+
+impl Material for RoughDielectric {
+    fn scatter(&self, r_in: &Ray, hit_record: &HitRecord) -> Option<(Ray, Color)> {
+        // Relative index of refraction of the interface: n_t / n_i
+        let eta = match hit_record.front_face() {
+            true => self.ir,
+            false => 1.0 / self.ir,
+        };
+
+        // The normal always faces the incoming ray, so wo is in the upper hemisphere.
+        let onb = Onb::new(hit_record.normal());
+        let wo = onb.to_local(&-r_in.direction().unit());
+        if wo.z() <= 0.0 {
+            return None;
+        }
+
+        let smooth = self.distribution.effectively_smooth();
+        let wm = match smooth {
+            true => Vec3::new(0.0, 0.0, 1.0),
+            false => self.distribution.sample_wm(&wo),
+        };
+
+        let reflectance = fr_dielectric(wo.dot(wm), eta);
+        let wi = match reflectance > fastrand::f64() {
+            true => {
+                let wi = (-wo).reflect(wm);
+                if wi.z() <= 0.0 {
+                    return None;
+                }
+                wi
+            }
+            false => {
+                let wi = refract(-wo, &wm, 1.0 / eta);
+                if wi.z() >= 0.0 {
+                    return None;
+                }
+                wi
+            }
+        };
+
+        let masking = match smooth {
+            true => 1.0,
+            false => self.distribution.g(&wo, &wi) / self.distribution.g1(&wo),
+        };
+        let attenuation =
+            masking * Self::ATTENUATION * transmittance(&self.absorption, r_in, hit_record);
+        let scattered = r_in.spawn(hit_record.p(), onb.to_world(&wi));
+
+        Some((scattered, attenuation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dielectric::Dielectric;
+    use crate::point3::Point3;
+    use crate::vec3::Length;
+
+    // Directions scattered by material for a ray hitting the y = 0 plane at the origin, with
+    // their attenuation
+    fn scatter(material: &dyn Material, direction: Vec3, samples: usize) -> Vec<(Vec3, Color)> {
+        let r_in = Ray::new(Point3::new(0.0, 0.0, 0.0) - direction, direction);
+        let hit_record = HitRecord::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material,
+            1.0,
+            &r_in,
+        );
+        (0..samples)
+            .filter_map(|_| material.scatter(&r_in, &hit_record))
+            .map(|(scattered, attenuation)| (scattered.direction().unit(), attenuation))
+            .collect()
+    }
+
+    #[test]
+    fn test_smooth() {
+        // Into the glass at 45 degrees, it reflects or refracts as the smooth dielectric does
+        let direction = Vec3::new(1.0, -1.0, 0.0).unit();
+        let smooth = scatter(&Dielectric::new(1.5), direction, 1000);
+        let rough = scatter(&RoughDielectric::new(1.5, 0.0), direction, 1000);
+        assert_eq!(1000, rough.len());
+        let reflected = Vec3::new(1.0, 1.0, 0.0).unit();
+        let refracted = refract(direction, &Vec3::new(0.0, 1.0, 0.0), 1.0 / 1.5);
+        for (expected, reflection) in [(reflected, true), (refracted, false)] {
+            let matches = |samples: &[(Vec3, Color)]| {
+                samples
+                    .iter()
+                    .filter(|(wi, _)| (wi - expected).length() < 1e-9)
+                    .count()
+            };
+            assert!(matches(&smooth) > 0);
+            assert!(matches(&rough) > 0);
+            if reflection {
+                // Fresnel reflectance is about 5% at 45 degrees
+                let fraction = matches(&rough) as f64 / 1000.0;
+                assert!((fraction - 0.05).abs() < 0.03, "{fraction}");
+            }
+        }
+        assert!(rough
+            .iter()
+            .all(|(wi, attenuation)| ((wi - reflected).length() < 1e-9
+                || (wi - refracted).length() < 1e-9)
+                && *attenuation == WHITE));
+    }
+
+    #[test]
+    fn test_total_internal_reflection() {
+        // Leaving the glass 60 degrees off the normal, past the critical angle of 42 degrees
+        let direction = Vec3::new(3_f64.sqrt() / 2.0, 0.5, 0.0);
+        let mirror = Vec3::new(3_f64.sqrt() / 2.0, -0.5, 0.0);
+        let samples = scatter(&RoughDielectric::new(1.5, 0.0), direction, 100);
+        assert_eq!(100, samples.len());
+        for (wi, attenuation) in samples {
+            assert!((wi - mirror).length() < 1e-9, "{wi:?}");
+            assert_eq!(WHITE, attenuation);
+        }
+    }
+}
@@ -1,28 +1,127 @@
+use std::ops::{Range, RangeInclusive};
+
 use crate::camera::Camera;
-use crate::color::Color;
+use crate::camera_path::CameraPath;
+use crate::color::{Color, WHITE};
 use crate::dielectric::Dielectric;
+use crate::film::Filter;
+use crate::fog::Fog;
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
 use crate::hittable_list::HittableList;
 use crate::lambertian::Lambertian;
 use crate::material::Material;
 use crate::metal::Metal;
+use crate::plane::Plane;
 use crate::point3::Point3;
+use crate::ray::Ray;
 use crate::sphere::Sphere;
-use crate::vec3::{Length, Random, RandomRanged, Vec3};
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::vec3::{Length, MulAdd, Random, RandomRanged, Unit, Vec3};
+
+mod examples;
 
 pub struct Scene {
-    camera: Camera,
+    camera: Box<dyn Camera>,
     world: HittableList,
+    lights: HittableList,      // Emissive objects of the world worth sampling
+    background: Option<Color>, // Sky gradient when None
+    spectral: bool,            // Trace wavelengths instead of RGB colors
+    fog: Option<Fog>,          // Medium between the surfaces
+    filter: Filter,            // Reconstruction of the pixels from the samples
+    // Animation of the camera, see set_frame()
+    camera_path: Option<CameraPath>,
 }
 
 impl Scene {
-    pub fn camera(&self) -> &Camera {
-        &self.camera
+    fn new(camera: impl Camera + 'static, world: HittableList) -> Self {
+        Self {
+            camera: Box::new(camera),
+            world,
+            lights: HittableList::default(),
+            background: None,
+            spectral: false,
+            fog: None,
+            filter: Filter::default(),
+            camera_path: None,
+        }
+    }
+
+    pub fn camera(&self) -> &dyn Camera {
+        &*self.camera
     }
 
     pub fn world(&self) -> &HittableList {
         &self.world
     }
 
+    // Closest interaction of r, on a surface of the world or in the fog before it.
+    pub fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        let hit_record = self.world.hit(r, t_range);
+        let Some(fog) = &self.fog else {
+            return hit_record;
+        };
+
+        let t_end = hit_record.as_ref().map_or(t_range.end, |h| h.t());
+        fog.hit(r, &(t_range.start..t_end)).or(hit_record)
+    }
+
+    pub fn lights(&self) -> &HittableList {
+        &self.lights
+    }
+
+    pub fn background(&self, r: &Ray) -> Color {
+        if let Some(background) = self.background {
+            return background;
+        }
+
+        let unit_direction = r.direction().unit();
+        let t = 0.5 * (unit_direction.y() + 1.0);
+
+        // (1.0 - t) * WHITE + t * Color::new(0.5, 0.7, 1.0)
+        WHITE.mul_add(1.0 - t, t * Color::new(0.5, 0.7, 1.0))
+    }
+
+    // Frames of the camera animation, if any
+    pub fn frames(&self) -> Option<RangeInclusive<usize>> {
+        self.camera_path.as_ref().map(CameraPath::frames)
+    }
+
+    // Move the camera to where its path is at frame, with the lens settings of the path
+    pub fn set_frame(&mut self, frame: usize) {
+        if let Some(camera_path) = &self.camera_path {
+            self.camera = Box::new(camera_path.camera(frame));
+        }
+    }
+
+    pub fn filter(&self) -> Filter {
+        self.filter
+    }
+
+    pub fn set_filter(&mut self, filter: Filter) {
+        self.filter = filter;
+    }
+
+    // Sample light, an emissive object also in the world, from the diffuse surfaces. Only the
+    // objects whose directions can be sampled, see Hittable::random(), are accepted, since the
+    // others would bias the mixture of light and material sampling.
+    pub fn add_light(&mut self, light: Box<dyn Hittable>) -> anyhow::Result<()> {
+        if light.random(&Point3::default()).is_none() {
+            anyhow::bail!("light that can't be sampled");
+        }
+        self.lights.add(light);
+
+        Ok(())
+    }
+
+    pub fn spectral(&self) -> bool {
+        self.spectral
+    }
+
+    pub fn set_spectral(&mut self, spectral: bool) {
+        self.spectral = spectral;
+    }
+
     pub fn three_spheres_custom_camera(aspect_ratio: f64) -> Self {
         // Camera
         let look_from = Point3::new(3.0, 3.0, 2.0);
@@ -30,9 +129,9 @@ impl Scene {
         let up = Vec3::new(0.0, 1.0, 0.0);
         let vertical_fov = 20.0;
         let aperture = 2.0;
-        let focus_distance = (look_from - look_at).length();
+        let focus_distance = 1.0; // Until the autofocus, once the world is built
 
-        let camera = Camera::new(
+        let camera = ThinLensCamera::new(
             look_from,
             look_at,
             up,
@@ -84,10 +183,13 @@ impl Scene {
         world.add(inner_hollow_ball);
         world.add(outer_hollow_ball);
 
-        Self { camera, world }
+        // Focus on the center ball, in the middle of the image
+        let (width, height) = (100, (100.0 / aspect_ratio) as usize);
+        let camera = camera.with_autofocus(&world, width, height, width / 2, height / 2);
+
+        Self::new(camera, world)
     }
 
-    #[allow(illegal_floating_point_literal_pattern)]
     pub fn one_weekend(aspect_ratio: f64) -> Self {
         // Camera
         let look_from = Point3::new(13.0, 2.0, 3.0);
@@ -97,7 +199,7 @@ impl Scene {
         let aperture = 0.1;
         let focus_distance = 10.0;
 
-        let camera = Camera::new(
+        let camera = ThinLensCamera::new(
             look_from,
             look_at,
             up,
@@ -115,9 +217,9 @@ impl Scene {
 
         // Ground
         let mut world = HittableList::default();
-        let ground = Box::new(Sphere::new(
-            Point3::new(0.0, -1000.0, 0.0),
-            1000.0,
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
             material_ground,
         ));
         world.add(ground);
@@ -185,6 +287,66 @@ impl Scene {
         ));
         world.add(metal_ball);
 
-        Self { camera, world }
+        Self::new(camera, world)
+    }
+
+    fn with_camera(self, camera: impl Camera + 'static) -> Self {
+        Self {
+            camera: Box::new(camera),
+            ..self
+        }
+    }
+
+    // Animate the camera along path, from its first frame
+    fn with_camera_path(self, camera_path: CameraPath) -> Self {
+        let camera = camera_path.camera(*camera_path.frames().start());
+
+        Self {
+            camera_path: Some(camera_path),
+            ..self.with_camera(camera)
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::color::BLACK;
+    use crate::diffuse_light::DiffuseLight;
+    use crate::orthographic_camera::OrthographicCamera;
+    use crate::quad::Quad;
+
+    // Square image of size world units, black but for its left half which is a white light,
+    // without noise
+    pub fn half_lit(size: f64) -> Scene {
+        let camera = OrthographicCamera::new(
+            Point3::new(0.0, 0.0, 1.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            size,
+            1.0,
+        );
+        let mut world = HittableList::default();
+        world.add(Box::new(Quad::new(
+            Point3::new(-size, -size, 0.0),
+            Vec3::new(size, 0.0, 0.0),
+            Vec3::new(0.0, 2.0 * size, 0.0),
+            Box::new(DiffuseLight::new(WHITE)),
+        )));
+
+        Scene {
+            background: Some(BLACK),
+            ..Scene::new(camera, world)
+        }
+    }
+
+    #[test]
+    fn test_add_light() {
+        let mut scene = Scene::cornell_box(1.0);
+        let light = || Box::new(DiffuseLight::new(Color::new(4.0, 4.0, 4.0)));
+        let ball = Sphere::new(Point3::new(0.0, 1.0, 0.0), 1.0, light());
+        assert!(scene.add_light(Box::new(ball)).is_ok());
+        let floor = Plane::new(Point3::default(), Vec3::new(0.0, 1.0, 0.0), light());
+        assert!(scene.add_light(Box::new(floor)).is_err());
     }
 }
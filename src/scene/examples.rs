@@ -0,0 +1,8 @@
+// Example scenes, one file per family of features
+
+mod animation;
+mod cameras;
+mod cornell;
+mod geometry;
+mod materials;
+mod volumes;
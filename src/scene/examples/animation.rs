@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use crate::aperture::{Aperture, AperturePolygon};
+use crate::camera::Camera;
+use crate::camera_path::{CameraPath, Interpolation, Keyframe, LensSettings};
+use crate::color::Color;
+use crate::conductor::Conductor;
+use crate::cuboid::Cuboid;
+use crate::hittable::Hittable;
+use crate::hittable_list::HittableList;
+use crate::instance::Instance;
+use crate::lambertian::Lambertian;
+use crate::plane::Plane;
+use crate::point3::Point3;
+use crate::scene::Scene;
+use crate::sphere::Sphere;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::transform::Transform;
+use crate::vec3::{Length, Vec3};
+
+impl Scene {
+    pub fn motion_blur(aspect_ratio: f64) -> Self {
+        // Camera, with the shutter open during the whole motion
+        let look_from = Point3::new(0.0, 3.0, 10.0);
+        let look_at = Point3::new(0.0, 1.0, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        )
+        .with_shutter(0.0, 1.0);
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Spheres bouncing up faster and faster, from left to right
+        for i in 0..4 {
+            let x = -3.0 + 1.5 * i as f64;
+            let motion = Vec3::new(0.0, 0.3 * i as f64, 0.0);
+            let sphere = Box::new(Sphere::moving(
+                Point3::new(x, 0.5, 0.0),
+                motion,
+                0.5,
+                Box::new(Lambertian::new(Color::new(0.7, 0.2, 0.2))),
+            ));
+            world.add(sphere);
+        }
+
+        // A still sphere for reference
+        let still = Box::new(Sphere::new(
+            Point3::new(3.0, 0.5, 0.0),
+            0.5,
+            Box::new(Conductor::gold(0.1)),
+        ));
+        world.add(still);
+
+        // A tilted cube sliding across the back
+        let cube: Arc<dyn Hittable> = Arc::new(Cuboid::new(
+            Point3::new(-0.5, -0.5, -0.5),
+            Point3::new(0.5, 0.5, 0.5),
+            Box::new(Lambertian::new(Color::new(0.2, 0.3, 0.7))),
+        ));
+        let transform = Transform::rotation(&Vec3::new(1.0, 1.0, 0.0), 30.0)
+            .then(&Transform::translation(&Vec3::new(-1.0, 1.8, -3.0)));
+        let motion = Vec3::new(2.0, 0.0, 0.0);
+        world.add(Box::new(Instance::moving(cube, transform, motion)));
+
+        Self::new(camera, world)
+    }
+
+    // Turntable around the primitives, a frame every 5 degrees
+    pub fn primitives_turntable(aspect_ratio: f64) -> Self {
+        let camera_path = CameraPath::turntable(
+            Point3::new(0.0, 4.0, 10.0),
+            Point3::new(0.0, 0.7, 0.0),
+            30.0,
+            aspect_ratio,
+            72,
+        )
+        .unwrap();
+
+        Self::primitives(aspect_ratio).with_camera_path(camera_path)
+    }
+
+    // Flight over the one weekend balls, zooming in on the big ones, 4 seconds at 24 fps through
+    // a cinema lens with 9 blades
+    pub fn one_weekend_flyby(aspect_ratio: f64) -> Self {
+        let keyframes = [
+            (
+                0,
+                Point3::new(13.0, 6.0, 12.0),
+                Point3::new(0.0, 0.0, 0.0),
+                35.0,
+            ),
+            (
+                30,
+                Point3::new(13.0, 2.0, 3.0),
+                Point3::new(0.0, 0.5, 0.0),
+                25.0,
+            ),
+            (
+                60,
+                Point3::new(6.0, 1.5, -6.0),
+                Point3::new(0.0, 1.0, 0.0),
+                25.0,
+            ),
+            (
+                95,
+                Point3::new(-4.0, 1.2, -5.0),
+                Point3::new(-4.0, 1.0, 0.0),
+                20.0,
+            ),
+        ];
+        let keyframes = keyframes
+            .into_iter()
+            .map(|(frame, look_from, look_at, vertical_fov)| Keyframe {
+                frame,
+                look_from,
+                look_at,
+                vertical_fov,
+                focus_distance: (look_from - look_at).length(),
+            })
+            .collect();
+        let camera_path = CameraPath::new(keyframes, Interpolation::CatmullRom, aspect_ratio)
+            .unwrap()
+            .with_aperture(0.1)
+            .with_lens(LensSettings {
+                aperture: Aperture::Polygonal(AperturePolygon::new(9, 0.0).unwrap()),
+                ..LensSettings::default()
+            });
+
+        Self::one_weekend(aspect_ratio).with_camera_path(camera_path)
+    }
+}
@@ -0,0 +1,287 @@
+use crate::aperture::{Aperture, ApertureMask, AperturePolygon};
+use crate::color::Color;
+use crate::conductor::Conductor;
+use crate::cuboid::Cuboid;
+use crate::diffuse_light::DiffuseLight;
+use crate::equirectangular_camera::EquirectangularCamera;
+use crate::fisheye_camera::{FisheyeCamera, FisheyeProjection};
+use crate::hittable_list::HittableList;
+use crate::image::Image;
+use crate::lambertian::Lambertian;
+use crate::omni_stereo_camera::OmniStereoCamera;
+use crate::orthographic_camera::OrthographicCamera;
+use crate::physical_camera::PhysicalParameters;
+use crate::plane::Plane;
+use crate::point3::Point3;
+use crate::realistic_camera::{LensSystem, RealisticCamera};
+use crate::scene::Scene;
+use crate::sphere::Sphere;
+use crate::stereo_camera::{StereoCamera, StereoLayout, StereoRig};
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::vec3::{Length, RandomRanged, Vec3};
+
+impl Scene {
+    // Isometric-like technical view of the primitives, without perspective
+    pub fn primitives_orthographic(aspect_ratio: f64) -> Self {
+        let camera = OrthographicCamera::new(
+            Point3::new(8.0, 6.5, 8.0),
+            Point3::new(0.0, 0.7, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            5.0,
+            aspect_ratio,
+        );
+
+        Self::primitives(aspect_ratio).with_camera(camera)
+    }
+
+    // The Cornell box through a 180 degrees fisheye, from just inside its opening
+    pub fn cornell_fisheye(aspect_ratio: f64) -> Self {
+        let camera = FisheyeCamera::new(
+            Point3::new(278.0, 278.0, 10.0),
+            Point3::new(278.0, 278.0, 555.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            180.0,
+            aspect_ratio,
+            FisheyeProjection::Equisolid,
+        );
+
+        Self::cornell_box(aspect_ratio).with_camera(camera)
+    }
+
+    // 360 degrees panorama from among the balls of one_weekend(), for a 2:1 image
+    pub fn panorama(aspect_ratio: f64) -> Self {
+        let camera = EquirectangularCamera::new(
+            Point3::new(0.0, 1.0, 3.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+
+        Self::one_weekend(aspect_ratio).with_camera(camera)
+    }
+
+    // Looking straight up at the clouds through an all-sky fisheye, angles to the zenith kept
+    pub fn clouds_all_sky(aspect_ratio: f64) -> Self {
+        let camera = FisheyeCamera::new(
+            Point3::new(0.0, 0.2, 0.0),
+            Point3::new(0.0, 10.0, 0.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            180.0,
+            aspect_ratio,
+            FisheyeProjection::Equidistant,
+        );
+
+        Self::clouds(aspect_ratio).with_camera(camera)
+    }
+
+    // one_weekend() as a side by side stereo pair, converging on the glass ball
+    pub fn one_weekend_stereo(aspect_ratio: f64) -> Self {
+        let look_from = Point3::new(13.0, 2.0, 3.0);
+        let look_at = Point3::new(0.0, 0.0, 0.0);
+        let rig = StereoRig {
+            interocular: 0.4,
+            convergence: (look_from - look_at).length(),
+            layout: StereoLayout::SideBySide,
+        };
+        let camera = StereoCamera::new(
+            look_from,
+            look_at,
+            Vec3::new(0.0, 1.0, 0.0),
+            20.0,
+            aspect_ratio,
+            0.1,
+            rig,
+        );
+
+        Self::one_weekend(aspect_ratio).with_camera(camera)
+    }
+
+    // panorama() in omni-directional stereo, for a 1:1 image with the left eye on top
+    pub fn panorama_stereo(aspect_ratio: f64) -> Self {
+        let camera = OmniStereoCamera::new(
+            Point3::new(0.0, 1.0, 3.0),
+            Point3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.065,
+            StereoLayout::TopBottom,
+        );
+
+        Self::panorama(aspect_ratio).with_camera(camera)
+    }
+
+    // Ball in focus in front of far away fairy lights, blurred into the shape of the aperture
+    fn bokeh_stage(aspect_ratio: f64, aperture: Aperture) -> Self {
+        // Camera, wide open
+        let look_from = Point3::new(0.0, 1.0, 6.0);
+        let look_at = Point3::new(0.0, 1.0, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture_size = 0.4;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture_size,
+            focus_distance,
+        )
+        .with_aperture(aperture)
+        .with_cat_eye(0.3);
+
+        let mut world = HittableList::default();
+        let ball = Box::new(Sphere::new(
+            Point3::new(0.0, 1.0, 0.0),
+            0.6,
+            Box::new(Conductor::gold(0.1)),
+        ));
+        world.add(ball);
+
+        // Lights scattered on a far wall
+        for _ in 0..60 {
+            let center = Point3::new(
+                f64::random_ranged(&(-12.0..12.0)),
+                f64::random_ranged(&(-4.0..7.0)),
+                f64::random_ranged(&(-30.0..-20.0)),
+            );
+            let warm = Color::new(
+                1.0,
+                0.7 + 0.3 * fastrand::f64(),
+                0.3 + 0.4 * fastrand::f64(),
+            );
+            let light = Box::new(Sphere::new(
+                center,
+                0.1,
+                Box::new(DiffuseLight::new(8.0 * warm)),
+            ));
+            world.add(light);
+        }
+
+        Self {
+            background: Some(Color::new(0.05, 0.05, 0.08)),
+            ..Self::new(camera, world)
+        }
+    }
+
+    pub fn bokeh(aspect_ratio: f64) -> Self {
+        let aperture = Aperture::Polygonal(AperturePolygon::new(6, 15.0).unwrap());
+
+        Self::bokeh_stage(aspect_ratio, aperture)
+    }
+
+    // Bokeh shaped after an image, light where the aperture is open.
+    pub fn bokeh_mask(aspect_ratio: f64, path: &str) -> anyhow::Result<Self> {
+        let mask = ApertureMask::new(&Image::load(path)?)?;
+
+        Ok(Self::bokeh_stage(aspect_ratio, Aperture::Mask(mask)))
+    }
+
+    // The Cornell box, in millimeters, as shot handheld in a dim room with a fast lens
+    pub fn cornell_box_physical(aspect_ratio: f64) -> Self {
+        let look_from = Point3::new(278.0, 278.0, -800.0);
+        let parameters = PhysicalParameters {
+            focal_length: 35.0,
+            f_stop: 2.0,
+            shutter_speed: 1.0 / 2.0,
+            iso: 800.0,
+            scene_scale: 1000.0,
+            ..PhysicalParameters::default()
+        };
+        let camera = ThinLensCamera::physical(
+            look_from,
+            Point3::new(278.0, 278.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            aspect_ratio,
+            1.0, // Until the autofocus
+            &parameters,
+        );
+
+        // Focus on the glass ball, below the middle of the image, at any aspect ratio. The
+        // sensor fits the width, so it's the same number of pixels away from the middle.
+        let scene = Self::cornell_box(aspect_ratio);
+        let (width, height) = (100, (100.0 / aspect_ratio) as usize);
+        let camera = camera.with_autofocus(scene.world(), width, height, 58, height / 2 + 18);
+
+        scene.with_camera(camera)
+    }
+
+    // The Cornell box, in millimeters, through the lens described by a prescription file
+    pub fn cornell_box_lens(aspect_ratio: f64, path: &str) -> anyhow::Result<Self> {
+        let look_from = Point3::new(278.0, 278.0, -1300.0);
+        let glass_ball = Point3::new(190.0, 90.0, 190.0);
+        let camera = RealisticCamera::new(
+            look_from,
+            Point3::new(278.0, 278.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            LensSystem::load(path)?,
+            43.3, // Full frame
+            aspect_ratio,
+            (glass_ball - look_from).length(),
+        )?
+        .with_scene_scale(1000.0)?;
+
+        Ok(Self::cornell_box(aspect_ratio).with_camera(camera))
+    }
+
+    // Balls of the one weekend scene as a miniature, with a tilted focal plane seen from above
+    pub fn one_weekend_miniature(aspect_ratio: f64) -> Self {
+        let look_from = Point3::new(13.0, 8.0, 3.0);
+        let look_at = Point3::new(0.0, 0.0, 0.0);
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            Vec3::new(0.0, 1.0, 0.0),
+            30.0,
+            aspect_ratio,
+            0.6,
+            (look_from - look_at).length(),
+        )
+        .with_tilt(-10.0, 0.0);
+
+        Self::one_weekend(aspect_ratio).with_camera(camera)
+    }
+
+    // Towers shot from the street with a level camera, shifted up to fit them with their
+    // verticals parallel
+    pub fn towers(aspect_ratio: f64) -> Self {
+        let look_from = Point3::new(0.0, 1.6, 24.0);
+        let look_at = Point3::new(0.0, 1.6, 0.0);
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            Vec3::new(0.0, 1.0, 0.0),
+            50.0,
+            aspect_ratio,
+            0.0,
+            (look_from - look_at).length(),
+        )
+        .with_shift(0.0, 0.4);
+
+        let mut world = HittableList::default();
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Box::new(Lambertian::new(Color::new(0.4, 0.4, 0.4))),
+        ));
+        world.add(ground);
+
+        let towers = [
+            (-9.0, -4.0, 12.0, Color::new(0.7, 0.6, 0.5)),
+            (-4.5, -8.0, 20.0, Color::new(0.5, 0.55, 0.6)),
+            (0.0, -2.0, 16.0, Color::new(0.75, 0.7, 0.65)),
+            (4.5, -7.0, 22.0, Color::new(0.45, 0.5, 0.55)),
+            (9.0, -3.0, 14.0, Color::new(0.6, 0.5, 0.45)),
+        ];
+        for (x, z, height, albedo) in towers {
+            let tower = Box::new(Cuboid::new(
+                Point3::new(x - 1.8, 0.0, z - 1.8),
+                Point3::new(x + 1.8, height, z + 1.8),
+                Box::new(Lambertian::new(albedo)),
+            ));
+            world.add(tower);
+        }
+
+        Self::new(camera, world)
+    }
+}
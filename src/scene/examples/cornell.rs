@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use crate::color::{Color, BLACK, WHITE};
+use crate::conductor::Conductor;
+use crate::constant_medium::ConstantMedium;
+use crate::cuboid::Cuboid;
+use crate::dielectric::Dielectric;
+use crate::diffuse_light::DiffuseLight;
+use crate::disk::Disk;
+use crate::hittable_list::HittableList;
+use crate::instance::Instance;
+use crate::lambertian::Lambertian;
+use crate::point3::Point3;
+use crate::quad::Quad;
+use crate::scene::Scene;
+use crate::sphere::Sphere;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::transform::Transform;
+use crate::vec3::Vec3;
+
+impl Scene {
+    // Empty Cornell box, lit by a ceiling light
+    fn cornell_room(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(278.0, 278.0, -800.0);
+        let look_at = Point3::new(278.0, 278.0, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 40.0;
+        let aperture = 0.0;
+        let focus_distance = 10.0;
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Materials
+        let red = Color::new(0.65, 0.05, 0.05);
+        let white = Color::new(0.73, 0.73, 0.73);
+        let green = Color::new(0.12, 0.45, 0.15);
+        let light = Color::new(15.0, 15.0, 15.0);
+
+        // Walls
+        let mut world = HittableList::default();
+        const SIZE: f64 = 555.0;
+        let walls = [
+            (
+                Point3::new(SIZE, 0.0, 0.0),
+                Vec3::new(0.0, SIZE, 0.0),
+                Vec3::new(0.0, 0.0, SIZE),
+                green,
+            ),
+            (
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, SIZE),
+                Vec3::new(0.0, SIZE, 0.0),
+                red,
+            ),
+            (
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, SIZE),
+                Vec3::new(SIZE, 0.0, 0.0),
+                white,
+            ),
+            (
+                Point3::new(SIZE, SIZE, SIZE),
+                Vec3::new(-SIZE, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -SIZE),
+                white,
+            ),
+            (
+                Point3::new(0.0, 0.0, SIZE),
+                Vec3::new(0.0, SIZE, 0.0),
+                Vec3::new(SIZE, 0.0, 0.0),
+                white,
+            ),
+        ];
+        for (q, u, v, albedo) in walls {
+            let wall = Box::new(Quad::new(q, u, v, Box::new(Lambertian::new(albedo))));
+            world.add(wall);
+        }
+
+        // Ceiling light, facing down
+        let light_quad = || {
+            Box::new(Quad::new(
+                Point3::new(343.0, 554.0, 332.0),
+                Vec3::new(-130.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -105.0),
+                Box::new(DiffuseLight::new(light)),
+            ))
+        };
+        world.add(light_quad());
+
+        let mut scene = Self {
+            background: Some(Color::default()),
+            ..Self::new(camera, world)
+        };
+        scene.add_light(light_quad()).unwrap();
+
+        scene
+    }
+
+    pub fn cornell_box(aspect_ratio: f64) -> Self {
+        let mut scene = Self::cornell_room(aspect_ratio);
+        let white = Color::new(0.73, 0.73, 0.73);
+
+        // A round mirror on the red wall and a couple of balls
+        let mirror = Box::new(Disk::new(
+            Point3::new(1.0, 300.0, 350.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            100.0,
+            Box::new(Conductor::silver(0.05)),
+        ));
+        scene.world.add(mirror);
+
+        let glass_ball = Box::new(Sphere::new(
+            Point3::new(190.0, 90.0, 190.0),
+            90.0,
+            Box::new(Dielectric::new(1.5)),
+        ));
+        scene.world.add(glass_ball);
+
+        let diffuse_ball = Box::new(Sphere::new(
+            Point3::new(380.0, 120.0, 350.0),
+            120.0,
+            Box::new(Lambertian::new(white)),
+        ));
+        scene.world.add(diffuse_ball);
+
+        scene
+    }
+
+    pub fn cornell_smoke(aspect_ratio: f64) -> Self {
+        let mut scene = Self::cornell_room(aspect_ratio);
+        let world = &mut scene.world;
+
+        // A tall block of dark smoke and a short one of light fog, rotated like in the classic box
+        let blocks = [
+            (
+                Vec3::new(165.0, 330.0, 165.0),
+                15.0,
+                Vec3::new(265.0, 0.0, 295.0),
+                BLACK,
+            ),
+            (
+                Vec3::new(165.0, 165.0, 165.0),
+                -18.0,
+                Vec3::new(130.0, 0.0, 65.0),
+                WHITE,
+            ),
+        ];
+        for (size, angle, offset, albedo) in blocks {
+            let block = Arc::new(Cuboid::new(
+                Point3::new(0.0, 0.0, 0.0),
+                size,
+                Box::new(Lambertian::new(WHITE)),
+            ));
+            let transform = Transform::rotation(&Vec3::new(0.0, 1.0, 0.0), angle)
+                .then(&Transform::translation(&offset));
+            let boundary = Box::new(Instance::new(block, transform));
+            world.add(Box::new(ConstantMedium::new(boundary, 0.01, albedo)));
+        }
+
+        scene
+    }
+}
@@ -0,0 +1,392 @@
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::conductor::Conductor;
+use crate::cone::Cone;
+use crate::csg::Csg;
+use crate::cuboid::Cuboid;
+use crate::cylinder::Cylinder;
+use crate::dielectric::Dielectric;
+use crate::fog::Fog;
+use crate::heightfield::Heightfield;
+use crate::hittable::Hittable;
+use crate::hittable_list::HittableList;
+use crate::image::Image;
+use crate::instance::Instance;
+use crate::lambertian::Lambertian;
+use crate::metal::Metal;
+use crate::plane::Plane;
+use crate::point3::Point3;
+use crate::scene::Scene;
+use crate::sdf::{self, Sdf, SdfHittable};
+use crate::sphere::Sphere;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::torus::Torus;
+use crate::transform::Transform;
+use crate::vec3::{Length, Vec3};
+
+impl Scene {
+    pub fn primitives(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 4.0, 10.0);
+        let look_at = Point3::new(0.0, 0.7, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        let cuboid = Box::new(Cuboid::new(
+            Point3::new(-3.6, 0.0, -0.6),
+            Point3::new(-2.4, 1.2, 0.6),
+            Box::new(Lambertian::new(Color::new(0.7, 0.2, 0.2))),
+        ));
+        world.add(cuboid);
+
+        let cylinder = Box::new(Cylinder::new(
+            Point3::new(-1.0, 0.0, 0.0),
+            0.6,
+            1.4,
+            Box::new(Conductor::copper(0.3)),
+        ));
+        world.add(cylinder);
+
+        let cone = Box::new(Cone::new(
+            Point3::new(0.8, 0.0, 0.0),
+            0.7,
+            1.6,
+            Box::new(Lambertian::new(Color::new(0.2, 0.3, 0.7))),
+        ));
+        world.add(cone);
+
+        let torus = Box::new(Torus::new(
+            Point3::new(2.9, 0.3, 0.0),
+            0.8,
+            0.3,
+            Box::new(Conductor::gold(0.1)),
+        ));
+        world.add(torus);
+
+        Self::new(camera, world)
+    }
+
+    pub fn instances(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 5.0, 12.0);
+        let look_at = Point3::new(0.0, 1.5, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // A helix of copies of the same torus, spinning and growing as they go up
+        let torus: Arc<dyn Hittable> = Arc::new(Torus::new(
+            Point3::new(0.0, 0.0, 0.0),
+            0.4,
+            0.12,
+            Box::new(Conductor::gold(0.2)),
+        ));
+        let count = 48;
+        for i in 0..count {
+            let step = i as f64 / count as f64;
+            let angle = 3.0 * 360.0 * step;
+            let scale = 0.6 + 0.6 * step;
+            let transform = Transform::scaling(&Vec3::new(scale, scale, scale))
+                .unwrap()
+                .then(&Transform::rotation(
+                    &Vec3::new(1.0, 0.0, 0.0),
+                    90.0 + angle,
+                ))
+                .then(&Transform::translation(&Vec3::new(
+                    2.5,
+                    0.4 + 3.0 * step,
+                    0.0,
+                )))
+                .then(&Transform::rotation(&Vec3::new(0.0, 1.0, 0.0), angle));
+            world.add(Box::new(Instance::new(torus.clone(), transform)));
+        }
+
+        // A unit cube, stretched into a pillar and tilted
+        let cube: Arc<dyn Hittable> = Arc::new(Cuboid::new(
+            Point3::new(-0.5, -0.5, -0.5),
+            Point3::new(0.5, 0.5, 0.5),
+            Box::new(Lambertian::new(Color::new(0.2, 0.3, 0.7))),
+        ));
+        let transform = Transform::scaling(&Vec3::new(0.8, 3.0, 0.8))
+            .unwrap()
+            .then(&Transform::rotation(&Vec3::new(0.0, 1.0, 0.0), 45.0))
+            .then(&Transform::rotation(&Vec3::new(0.0, 0.0, 1.0), 10.0))
+            .then(&Transform::translation(&Vec3::new(0.0, 1.5, 0.0)));
+        world.add(Box::new(Instance::new(cube, transform)));
+
+        Self::new(camera, world)
+    }
+
+    pub fn csg(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 4.0, 10.0);
+        let look_at = Point3::new(0.0, 0.7, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Biconvex lens: the intersection of two overlapping spheres
+        let glass = || Box::new(Dielectric::new(1.5));
+        let lens = Box::new(Csg::intersection(
+            Box::new(Sphere::new(Point3::new(-3.0, 1.1, 1.7), 2.0, glass())),
+            Box::new(Sphere::new(Point3::new(-3.0, 1.1, -1.7), 2.0, glass())),
+        ));
+        world.add(lens);
+
+        // Bowl: a spherical shell cut in half
+        let red = || Box::new(Lambertian::new(Color::new(0.7, 0.2, 0.2)));
+        let shell = Box::new(Csg::difference(
+            Box::new(Sphere::new(Point3::new(0.0, 1.0, 0.0), 1.0, red())),
+            Box::new(Sphere::new(Point3::new(0.0, 1.0, 0.0), 0.9, red())),
+        ));
+        let bowl = Box::new(Csg::intersection(
+            shell,
+            Box::new(Cuboid::new(
+                Point3::new(-1.0, 0.0, -1.0),
+                Point3::new(1.0, 1.0, 1.0),
+                red(),
+            )),
+        ));
+        world.add(bowl);
+
+        // Block drilled through by a vertical and a horizontal hole
+        let copper = || Box::new(Conductor::copper(0.3));
+        let block = Box::new(Cuboid::new(
+            Point3::new(2.2, 0.0, -0.8),
+            Point3::new(3.8, 1.2, 0.8),
+            copper(),
+        ));
+        let vertical_drill = Box::new(Cylinder::new(
+            Point3::new(3.0, -0.1, 0.0),
+            0.35,
+            1.4,
+            copper(),
+        ));
+        let horizontal_drill = Box::new(Instance::new(
+            Arc::new(Cylinder::new(
+                Point3::new(0.0, 0.0, 0.0),
+                0.25,
+                1.8,
+                copper(),
+            )),
+            Transform::rotation(&Vec3::new(0.0, 0.0, 1.0), -90.0)
+                .then(&Transform::translation(&Vec3::new(2.1, 0.6, 0.0))),
+        ));
+        let drills = Box::new(Csg::union(vertical_drill, horizontal_drill));
+        world.add(Box::new(Csg::difference(block, drills)));
+
+        Self::new(camera, world)
+    }
+
+    pub fn sdf(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 4.0, 10.0);
+        let look_at = Point3::new(0.0, 0.8, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Rounded die, trimmed by a sphere and drilled through
+        let center = Point3::new(-3.6, 0.7, 0.0);
+        let die = sdf::subtract(
+            sdf::intersection(
+                sdf::rounded_box(center, Vec3::new(0.6, 0.6, 0.6), 0.1),
+                sdf::sphere(center, 0.8),
+            ),
+            sdf::capsule(
+                center - Vec3::new(1.0, 0.0, 0.0),
+                center + Vec3::new(1.0, 0.0, 0.0),
+                0.25,
+            ),
+        );
+        let material = Box::new(Lambertian::new(Color::new(0.2, 0.3, 0.7)));
+        world.add(Box::new(SdfHittable::new(die, center, 1.0, material)));
+
+        // Blob of smoothly merged balls
+        let center = Point3::new(-1.2, 0.8, 0.0);
+        let blob = sdf::smooth_union(
+            sdf::smooth_union(
+                sdf::sphere(center + Vec3::new(-0.3, -0.2, 0.0), 0.5),
+                sdf::sphere(center + Vec3::new(0.35, -0.3, 0.1), 0.4),
+                0.3,
+            ),
+            sdf::sphere(center + Vec3::new(0.0, 0.4, -0.1), 0.35),
+            0.3,
+        );
+        let material = Box::new(Dielectric::new(1.5));
+        world.add(Box::new(SdfHittable::new(blob, center, 1.2, material)));
+
+        // Ball grooved by a torus, on a stem
+        let center = Point3::new(1.2, 1.0, 0.0);
+        let grooved_ball = sdf::union(
+            sdf::smooth_subtract(sdf::sphere(center, 0.6), sdf::torus(center, 0.6, 0.15), 0.1),
+            sdf::capsule(Point3::new(1.2, 0.0, 0.0), center, 0.1),
+        );
+        let material = Box::new(Conductor::copper(0.2));
+        world.add(Box::new(SdfHittable::new(
+            grooved_ball,
+            center,
+            1.1,
+            material,
+        )));
+
+        // Any closure is a distance function: a Mandelbulb moved and scaled
+        let center = Point3::new(3.6, 1.0, 0.0);
+        let scale = 0.8;
+        let bulb = sdf::mandelbulb(8.0, 12);
+        let fractal = move |p: &Point3| scale * bulb.distance(&((p - center) / scale));
+        let material = Box::new(Conductor::gold(0.3));
+        world.add(Box::new(SdfHittable::new(
+            fractal,
+            center,
+            1.2 * scale,
+            material,
+        )));
+
+        Self::new(camera, world)
+    }
+
+    // Landscape of a heightfield stretched over 20x20 units, with a lake
+    fn landscape(aspect_ratio: f64, heightfield: Heightfield) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 5.0, 14.0);
+        let look_at = Point3::new(0.0, 0.5, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 45.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        let mut world = HittableList::default();
+        let transform = Transform::scaling(&Vec3::new(20.0, 4.0, 20.0))
+            .unwrap()
+            .then(&Transform::translation(&Vec3::new(-10.0, -0.5, -10.0)));
+        world.add(Box::new(Instance::new(Arc::new(heightfield), transform)));
+
+        let lake = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Box::new(Metal::new(Color::new(0.2, 0.35, 0.5), 0.05)),
+        ));
+        world.add(lake);
+
+        // Light haze towards the horizon
+        let fog = Fog::new(0.01, 0.0, 0.3, 60.0);
+
+        Self {
+            fog: Some(fog),
+            ..Self::new(camera, world)
+        }
+    }
+
+    pub fn terrain(aspect_ratio: f64) -> Self {
+        let material = Box::new(Lambertian::new(Color::new(0.35, 0.45, 0.25)));
+        let heightfield = Heightfield::from_noise(256, 4.0, material).unwrap();
+
+        Self::landscape(aspect_ratio, heightfield)
+    }
+
+    // Terrain from the luminance of a PGM or PPM image.
+    pub fn terrain_from_image(aspect_ratio: f64, path: &str) -> anyhow::Result<Self> {
+        let image = Image::load(path)?;
+        let material = Box::new(Lambertian::new(Color::new(0.35, 0.45, 0.25)));
+        let heightfield = Heightfield::from_image(&image, material)?;
+
+        Ok(Self::landscape(aspect_ratio, heightfield))
+    }
+}
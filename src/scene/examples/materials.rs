@@ -0,0 +1,339 @@
+use crate::color::Color;
+use crate::conductor::Conductor;
+use crate::dielectric::{Dielectric, Dispersion};
+use crate::hittable_list::HittableList;
+use crate::lambertian::Lambertian;
+use crate::material::Material;
+use crate::plane::Plane;
+use crate::point3::Point3;
+use crate::principled::{Principled, PrincipledParameters};
+use crate::rough_dielectric::RoughDielectric;
+use crate::scene::Scene;
+use crate::sphere::Sphere;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::vec3::{Length, Vec3};
+
+impl Scene {
+    pub fn metals(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 6.0, 8.0);
+        let look_at = Point3::new(0.0, 0.0, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.05;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // One column per metal, from polished (back) to rough (front)
+        const BALL_RADIUS: f64 = 0.4;
+        const ROUGHNESSES: [f64; 4] = [0.0, 0.2, 0.4, 0.7];
+        let metals: [fn(f64) -> Conductor; 4] = [
+            Conductor::gold,
+            Conductor::copper,
+            Conductor::aluminium,
+            Conductor::silver,
+        ];
+        for (i, metal) in metals.iter().enumerate() {
+            for (j, roughness) in ROUGHNESSES.iter().enumerate() {
+                let center = Point3::new(i as f64 - 1.5, BALL_RADIUS, j as f64 - 1.5);
+                let ball = Box::new(Sphere::new(
+                    center,
+                    BALL_RADIUS,
+                    Box::new(metal(*roughness)),
+                ));
+                world.add(ball);
+            }
+        }
+
+        Self::new(camera, world)
+    }
+
+    pub fn frosted_glass(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 1.5, 8.0);
+        let look_at = Point3::new(0.0, 0.6, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Colorful balls behind the glass, to show how blurry the transmission is
+        for i in 0..12 {
+            let albedo = Color::new(
+                0.5 + 0.4 * (i as f64).sin(),
+                0.5 + 0.4 * (i as f64 * 1.7).cos(),
+                0.5 + 0.4 * (i as f64 * 0.6).sin(),
+            );
+            let ball = Box::new(Sphere::new(
+                Point3::new(0.6 * i as f64 - 3.3, 0.25, -2.0),
+                0.25,
+                Box::new(Lambertian::new(albedo)),
+            ));
+            world.add(ball);
+        }
+
+        // From smooth (left) to frosted (right)
+        const BALL_RADIUS: f64 = 0.6;
+        let materials: [Box<dyn Material>; 4] = [
+            Box::new(Dielectric::new(1.5)),
+            Box::new(RoughDielectric::new(1.5, 0.1)),
+            Box::new(RoughDielectric::new(1.5, 0.3)),
+            Box::new(RoughDielectric::new(1.5, 0.6)),
+        ];
+        for (i, material) in materials.into_iter().enumerate() {
+            let ball = Box::new(Sphere::new(
+                Point3::new(1.5 * i as f64 - 2.25, BALL_RADIUS, 0.0),
+                BALL_RADIUS,
+                material,
+            ));
+            world.add(ball);
+        }
+
+        Self::new(camera, world)
+    }
+
+    pub fn tinted_glass(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 4.0, 10.0);
+        let look_at = Point3::new(0.0, 0.5, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.8, 0.8, 0.8)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Same glass, different thickness: the bigger the ball the deeper the color
+        const TINT_DISTANCE: f64 = 1.0;
+        let emerald = Color::new(0.3, 0.8, 0.5);
+        let ruby = Color::new(0.9, 0.2, 0.3);
+        const BALLS: [(f64, f64); 4] = [(-2.7, 0.25), (-1.7, 0.5), (-0.2, 0.75), (1.8, 1.0)];
+        for (x, radius) in BALLS {
+            let glass = Box::new(Sphere::new(
+                Point3::new(x, radius, -1.5),
+                radius,
+                Box::new(Dielectric::tinted(1.5, emerald, TINT_DISTANCE).unwrap()),
+            ));
+            world.add(glass);
+
+            let frosted = Box::new(Sphere::new(
+                Point3::new(x + 1.0, radius, 1.5),
+                radius,
+                Box::new(RoughDielectric::tinted(1.7, 0.2, ruby, TINT_DISTANCE).unwrap()),
+            ));
+            world.add(frosted);
+        }
+
+        Self::new(camera, world)
+    }
+
+    pub fn dispersion(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 1.0, 8.0);
+        let look_at = Point3::new(0.0, 0.8, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 35.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.8, 0.8, 0.8)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        // Black and white stripes behind the balls, where the colored fringes stand out
+        for i in 0..16 {
+            let albedo = match i % 2 {
+                0 => Color::new(0.05, 0.05, 0.05),
+                _ => Color::new(0.9, 0.9, 0.9),
+            };
+            let stripe = Box::new(Sphere::new(
+                Point3::new(0.5 * i as f64 - 3.75, 0.25, -3.0),
+                0.25,
+                Box::new(Lambertian::new(albedo)),
+            ));
+            world.add(stripe);
+        }
+
+        // From the least (left) to the most (right) dispersive
+        const BALL_RADIUS: f64 = 0.8;
+        let dispersions = [
+            Dispersion::BK7,
+            Dispersion::DIAMOND,
+            Dispersion::SF11,
+            Dispersion::Cauchy { a: 1.6, b: 0.05 },
+        ];
+        for (i, dispersion) in dispersions.into_iter().enumerate() {
+            let ball = Box::new(Sphere::new(
+                Point3::new(1.8 * i as f64 - 2.7, BALL_RADIUS, 0.0),
+                BALL_RADIUS,
+                Box::new(Dielectric::dispersive(dispersion)),
+            ));
+            world.add(ball);
+        }
+
+        Self {
+            spectral: true,
+            ..Self::new(camera, world)
+        }
+    }
+
+    pub fn principled(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 4.0, 10.0);
+        let look_at = Point3::new(0.0, 0.5, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        const BALL_RADIUS: f64 = 0.6;
+        let orange = Color::new(0.9, 0.4, 0.1);
+
+        // Back row: from dielectric (left) to metallic (right)
+        for i in 0..5 {
+            let metallic = i as f64 / 4.0;
+            let material = Principled::metallic_roughness(orange, metallic, 0.3, 1.45, 0.0);
+            let ball = Box::new(Sphere::new(
+                Point3::new(1.5 * i as f64 - 3.0, BALL_RADIUS, -1.0),
+                BALL_RADIUS,
+                Box::new(material.unwrap()),
+            ));
+            world.add(ball);
+        }
+
+        // Front row: default, clearcoat, sheen, glass and frosted glass
+        let front_row = [
+            PrincipledParameters::default(),
+            PrincipledParameters {
+                base_color: Color::new(0.1, 0.2, 0.6),
+                roughness: 0.6,
+                clearcoat: 1.0,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: Color::new(0.5, 0.1, 0.2),
+                roughness: 1.0,
+                specular: 0.0,
+                sheen: 1.0,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: Color::new(1.0, 1.0, 1.0),
+                roughness: 0.0,
+                transmission: 1.0,
+                ior: 1.5,
+                ..Default::default()
+            },
+            PrincipledParameters {
+                base_color: Color::new(0.6, 0.9, 0.7),
+                roughness: 0.3,
+                transmission: 1.0,
+                ior: 1.5,
+                ..Default::default()
+            },
+        ];
+        for (i, parameters) in front_row.into_iter().enumerate() {
+            let ball = Box::new(Sphere::new(
+                Point3::new(1.5 * i as f64 - 3.0, BALL_RADIUS, 1.0),
+                BALL_RADIUS,
+                Box::new(Principled::new(parameters)),
+            ));
+            world.add(ball);
+        }
+
+        Self::new(camera, world)
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::Arc;
+
+use crate::color::{Color, WHITE};
+use crate::cuboid::Cuboid;
+use crate::cylinder::Cylinder;
+use crate::density::{DensityGrid, NoiseDensity};
+use crate::diffuse_light::DiffuseLight;
+use crate::fog::Fog;
+use crate::heterogeneous_medium::HeterogeneousMedium;
+use crate::hittable_list::HittableList;
+use crate::instance::Instance;
+use crate::lambertian::Lambertian;
+use crate::perlin::Perlin;
+use crate::plane::Plane;
+use crate::point3::Point3;
+use crate::quad::Quad;
+use crate::scene::Scene;
+use crate::sphere::Sphere;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::transform::Transform;
+use crate::vec3::{Length, Vec3};
+
+impl Scene {
+    // Ground under a clear sky, to show off volumes
+    fn volume_stage(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(0.0, 2.0, 12.0);
+        let look_at = Point3::new(0.0, 1.5, 0.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 30.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // Ground
+        let mut world = HittableList::default();
+        let material_ground = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let ground = Box::new(Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            material_ground,
+        ));
+        world.add(ground);
+
+        Self::new(camera, world)
+    }
+
+    // Unit cube boundary of a density grid, scaled by size with its bottom center at position.
+    fn grid_volume(grid: DensityGrid, size: f64, position: Vec3, albedo: Color) -> Box<Instance> {
+        let cube = Box::new(Cuboid::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 1.0),
+            Box::new(Lambertian::new(WHITE)),
+        ));
+        let medium = Arc::new(HeterogeneousMedium::new(cube, Box::new(grid), albedo));
+        let transform = Transform::translation(&Vec3::new(-0.5, 0.0, -0.5))
+            .then(&Transform::scaling(&Vec3::new(size, size, size)).unwrap())
+            .then(&Transform::translation(&position));
+
+        Box::new(Instance::new(medium, transform))
+    }
+
+    pub fn clouds(aspect_ratio: f64) -> Self {
+        let mut scene = Self::volume_stage(aspect_ratio);
+
+        // A puffy cloud of Perlin turbulence in a squashed sphere
+        let boundary = Box::new(Sphere::new(
+            Point3::new(0.0, 0.0, 0.0),
+            1.0,
+            Box::new(Lambertian::new(WHITE)),
+        ));
+        let cloud = Arc::new(HeterogeneousMedium::new(
+            boundary,
+            Box::new(NoiseDensity::new(6.0, 2.5)),
+            Color::new(0.9, 0.9, 0.9),
+        ));
+        let transform = Transform::scaling(&Vec3::new(2.0, 1.2, 1.5))
+            .unwrap()
+            .then(&Transform::translation(&Vec3::new(-2.2, 2.0, 0.0)));
+        scene.world.add(Box::new(Instance::new(cloud, transform)));
+
+        // A procedural billow on a voxel grid, dense at its core and frayed by noise
+        const N: usize = 48;
+        let noise = Perlin::new();
+        let mut values = Vec::with_capacity(N * N * N);
+        for z in 0..N {
+            for y in 0..N {
+                for x in 0..N {
+                    let voxel = Point3::new(x as f64 + 0.5, y as f64 + 0.5, z as f64 + 0.5);
+                    let p = voxel / N as f64;
+                    let radius = (p - Point3::new(0.5, 0.5, 0.5)).length();
+                    let falloff = (1.0 - radius / 0.5).max(0.0);
+                    let turbulence = noise.turbulence(&(4.0 * p), 5);
+                    values.push((30.0 * falloff * (0.3 + turbulence)) as f32);
+                }
+            }
+        }
+        let grid = DensityGrid::new([N, N, N], values).unwrap();
+        let billow = Self::grid_volume(
+            grid,
+            3.0,
+            Vec3::new(2.2, 0.3, 0.0),
+            Color::new(0.7, 0.5, 0.4),
+        );
+        scene.world.add(billow);
+
+        scene
+    }
+
+    // Density grid loaded from a file, see DensityGrid::read() for the format.
+    pub fn density_grid(aspect_ratio: f64, path: &str) -> anyhow::Result<Self> {
+        let mut scene = Self::volume_stage(aspect_ratio);
+
+        let grid = DensityGrid::load(path)?;
+        let volume = Self::grid_volume(
+            grid,
+            3.0,
+            Vec3::new(0.0, 0.0, 0.0),
+            Color::new(0.8, 0.8, 0.8),
+        );
+        scene.world.add(volume);
+
+        Ok(scene)
+    }
+
+    pub fn god_rays(aspect_ratio: f64) -> Self {
+        // Camera
+        let look_from = Point3::new(1.0, 2.5, 1.0);
+        let look_at = Point3::new(9.0, 2.0, 6.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        let vertical_fov = 60.0;
+        let aperture = 0.0;
+        let focus_distance = (look_from - look_at).length();
+
+        let camera = ThinLensCamera::new(
+            look_from,
+            look_at,
+            up,
+            vertical_fov,
+            aspect_ratio,
+            aperture,
+            focus_distance,
+        );
+
+        // A closed room, with a window in the wall at x = 10
+        let mut world = HittableList::default();
+        let white = Color::new(0.73, 0.73, 0.73);
+        let walls = [
+            // Floor, ceiling and the walls without window
+            (
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ),
+            (
+                Point3::new(0.0, 6.0, 0.0),
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ),
+            (
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 6.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ),
+            (
+                Point3::new(0.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(0.0, 6.0, 0.0),
+            ),
+            (
+                Point3::new(0.0, 0.0, 10.0),
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(0.0, 6.0, 0.0),
+            ),
+            // The window wall, around a hole with y in [3, 5] and z in [4, 6]
+            (
+                Point3::new(10.0, 0.0, 0.0),
+                Vec3::new(0.0, 3.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ),
+            (
+                Point3::new(10.0, 5.0, 0.0),
+                Vec3::new(0.0, 1.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ),
+            (
+                Point3::new(10.0, 3.0, 0.0),
+                Vec3::new(0.0, 2.0, 0.0),
+                Vec3::new(0.0, 0.0, 4.0),
+            ),
+            (
+                Point3::new(10.0, 3.0, 6.0),
+                Vec3::new(0.0, 2.0, 0.0),
+                Vec3::new(0.0, 0.0, 4.0),
+            ),
+        ];
+        for (q, u, v) in walls {
+            let wall = Box::new(Quad::new(q, u, v, Box::new(Lambertian::new(white))));
+            world.add(wall);
+        }
+
+        let pillar = Box::new(Cylinder::new(
+            Point3::new(5.0, 0.0, 5.0),
+            0.4,
+            6.0,
+            Box::new(Lambertian::new(white)),
+        ));
+        world.add(pillar);
+
+        // Sun-like light high up outside, facing down
+        let sun = || {
+            Box::new(Quad::new(
+                Point3::new(24.0, 16.0, 9.0),
+                Vec3::new(-8.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -8.0),
+                Box::new(DiffuseLight::new(Color::new(25.0, 23.0, 18.0))),
+            ))
+        };
+        world.add(sun());
+
+        // Slightly forward scattering haze
+        let fog = Fog::new(0.06, 0.01, 0.6, 100.0);
+
+        let mut scene = Self {
+            background: Some(Color::default()),
+            fog: Some(fog),
+            ..Self::new(camera, world)
+        };
+        scene.add_light(sun()).unwrap();
+
+        scene
+    }
+}
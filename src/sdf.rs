@@ -0,0 +1,239 @@
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Length, LengthSquared, Unit, Vec3};
+
+// Signed distance function: distance from p to the surface, negative inside.
+//
+// It may underestimate the distance, which only slows the tracing down, but never overestimate
+// it. Any closure of the right signature is one.
+pub trait Sdf: Send + Sync {
+    fn distance(&self, p: &Point3) -> f64;
+}
+
+impl<F> Sdf for F
+where
+    F: Fn(&Point3) -> f64 + Send + Sync,
+{
+    fn distance(&self, p: &Point3) -> f64 {
+        self(p)
+    }
+}
+
+// Built-in primitives
+
+pub fn sphere(center: Point3, radius: f64) -> impl Sdf {
+    move |p: &Point3| (p - center).length() - radius
+}
+
+// Box of the given half extents, with its edges rounded by radius.
+pub fn rounded_box(center: Point3, half_extents: Vec3, radius: f64) -> impl Sdf {
+    move |p: &Point3| {
+        let p = p - center;
+        let q = Vec3::new(
+            p.x().abs() - half_extents.x() + radius,
+            p.y().abs() - half_extents.y() + radius,
+            p.z().abs() - half_extents.z() + radius,
+        );
+        let outside = Vec3::new(q.x().max(0.0), q.y().max(0.0), q.z().max(0.0)).length();
+        let inside = q.x().max(q.y()).max(q.z()).min(0.0);
+        outside + inside - radius
+    }
+}
+
+// Torus around the y axis.
+pub fn torus(center: Point3, major_radius: f64, minor_radius: f64) -> impl Sdf {
+    move |p: &Point3| {
+        let p = p - center;
+        let ring = (p.x() * p.x() + p.z() * p.z()).sqrt() - major_radius;
+        (ring * ring + p.y() * p.y()).sqrt() - minor_radius
+    }
+}
+
+// Segment from a to b, inflated by radius.
+pub fn capsule(a: Point3, b: Point3, radius: f64) -> impl Sdf {
+    move |p: &Point3| {
+        let pa = p - a;
+        let ba = b - a;
+        let h = (pa.dot(ba) / ba.length_squared()).clamp(0.0, 1.0);
+        (pa - h * ba).length() - radius
+    }
+}
+
+// Mandelbulb fractal of the given power, within the sphere of radius 1.2 around the origin.
+// Distance estimated from the derivative of the iteration.
+pub fn mandelbulb(power: f64, iterations: usize) -> impl Sdf {
+    move |p: &Point3| {
+        let mut z = *p;
+        let mut dr = 1.0;
+        let mut r = z.length();
+        for _ in 0..iterations {
+            if r > 2.0 {
+                break;
+            }
+
+            // z = z^power + p, in spherical coordinates
+            let theta = (z.z() / r).acos() * power;
+            let phi = z.y().atan2(z.x()) * power;
+            dr = r.powf(power - 1.0) * power * dr + 1.0;
+            let zr = r.powf(power);
+            z =
+                zr * Vec3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                ) + p;
+            r = z.length();
+        }
+
+        0.5 * r.ln() * r / dr
+    }
+}
+
+// Combinators
+
+pub fn union(a: impl Sdf, b: impl Sdf) -> impl Sdf {
+    move |p: &Point3| a.distance(p).min(b.distance(p))
+}
+
+pub fn intersection(a: impl Sdf, b: impl Sdf) -> impl Sdf {
+    move |p: &Point3| a.distance(p).max(b.distance(p))
+}
+
+// a with b carved out of it
+pub fn subtract(a: impl Sdf, b: impl Sdf) -> impl Sdf {
+    move |p: &Point3| a.distance(p).max(-b.distance(p))
+}
+
+// Union blending both surfaces within a distance k, with a polynomial smooth minimum.
+pub fn smooth_union(a: impl Sdf, b: impl Sdf, k: f64) -> impl Sdf {
+    move |p: &Point3| {
+        let (da, db) = (a.distance(p), b.distance(p));
+        let h = (0.5 + 0.5 * (db - da) / k).clamp(0.0, 1.0);
+        db + h * (da - db) - k * h * (1.0 - h)
+    }
+}
+
+// Subtraction with a fillet of size k along the cut.
+pub fn smooth_subtract(a: impl Sdf, b: impl Sdf, k: f64) -> impl Sdf {
+    move |p: &Point3| {
+        let (da, db) = (a.distance(p), b.distance(p));
+        let h = (0.5 - 0.5 * (da + db) / k).clamp(0.0, 1.0);
+        da + h * (-db - da) + k * h * (1.0 - h)
+    }
+}
+
+// Surface of a signed distance function, found by sphere tracing
+//
+// The ray advances by the distance to the surface, which is safe by definition, until it's
+// close enough. Rays are only marched inside a bounding sphere, that must contain the surface.
+// Normals are the gradient of the distance, by central differences.
+pub struct SdfHittable {
+    sdf: Box<dyn Sdf>,
+    center: Point3, // Bounding sphere
+    radius: f64,
+    material: Box<dyn Material>,
+}
+
+impl SdfHittable {
+    const MAX_STEPS: usize = 512;
+    const EPSILON: f64 = 1e-5;
+
+    pub fn new(
+        sdf: impl Sdf + 'static,
+        center: Point3,
+        radius: f64,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self {
+            sdf: Box::new(sdf),
+            center,
+            radius,
+            material,
+        }
+    }
+
+    fn normal(&self, p: &Point3) -> Vec3 {
+        let h = Self::EPSILON;
+        let derivative =
+            |axis: Vec3| self.sdf.distance(&(p + h * axis)) - self.sdf.distance(&(p - h * axis));
+
+        Vec3::new(
+            derivative(Vec3::new(1.0, 0.0, 0.0)),
+            derivative(Vec3::new(0.0, 1.0, 0.0)),
+            derivative(Vec3::new(0.0, 0.0, 1.0)),
+        )
+        .unit()
+    }
+}
+
+impl Hittable for SdfHittable {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Clip the ray to the bounding sphere
+        let oc = r.origin() - self.center;
+        let a = r.direction().length_squared();
+        let half_b = oc.dot(r.direction());
+        let c = oc.length_squared() - self.radius * self.radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let sqrtd = discriminant.sqrt();
+        let mut t = ((-half_b - sqrtd) / a).max(t_range.start);
+        let t_end = ((-half_b + sqrtd) / a).min(t_range.end);
+
+        // Rays starting inside the surface, refracted ones, march to it from within
+        let ray_length = a.sqrt();
+        let sign = self.sdf.distance(&r.at(t)).signum();
+        for _ in 0..Self::MAX_STEPS {
+            if t >= t_end {
+                return None;
+            }
+
+            let p = r.at(t);
+            let distance = sign * self.sdf.distance(&p);
+            if distance < Self::EPSILON {
+                let outward_normal = self.normal(&p);
+                return Some(HitRecord::new(p, outward_normal, &*self.material, t, r));
+            }
+            t += distance / ray_length;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let center = Point3::new(0.0, 0.0, -5.0);
+        let sdf = SdfHittable::new(sphere(center, 1.0), center, 1.5, material);
+
+        // From outside, with a non unit direction
+        let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -2.0));
+        let hit_record = sdf.hit(&r, &(0.001..f64::INFINITY)).unwrap();
+        assert!((hit_record.t() - 2.0).abs() < 1e-4);
+        assert!(hit_record.front_face());
+        assert!((hit_record.normal().z() - 1.0).abs() < 1e-4);
+
+        // From inside
+        let r = Ray::new(center, Vec3::new(1.0, 0.0, 0.0));
+        let hit_record = sdf.hit(&r, &(0.001..f64::INFINITY)).unwrap();
+        assert!((hit_record.t() - 1.0).abs() < 1e-4);
+        assert!(!hit_record.front_face());
+
+        // Missing
+        let r = Ray::new(Point3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+        assert!(sdf.hit(&r, &(0.001..f64::INFINITY)).is_none());
+    }
+}
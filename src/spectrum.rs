@@ -0,0 +1,330 @@
+use std::ops::{Add, Mul, MulAssign};
+use std::sync::OnceLock;
+
+use crate::color::Color;
+
+// Range of the visible spectrum that is sampled
+pub const LAMBDA_MIN: f64 = 380.0;
+pub const LAMBDA_MAX: f64 = 780.0;
+
+// Wavelengths carried by each path
+const N_SAMPLES: usize = 4;
+
+// Hero wavelength sampling
+//
+// Every path carries N_SAMPLES wavelengths: a uniformly sampled hero wavelength plus secondary
+// wavelengths evenly rotated over the spectrum. Wavelength dependent events, like dispersion,
+// follow the hero wavelength and terminate the secondary ones.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SampledWavelengths {
+    lambda: [f64; N_SAMPLES],
+    pdf: [f64; N_SAMPLES],
+}
+
+impl SampledWavelengths {
+    pub fn sample() -> Self {
+        let range = LAMBDA_MAX - LAMBDA_MIN;
+        let hero = fastrand::f64();
+
+        let mut lambda = [0.0; N_SAMPLES];
+        for (i, l) in lambda.iter_mut().enumerate() {
+            let offset = (hero + i as f64 / N_SAMPLES as f64).fract();
+            *l = range.mul_add(offset, LAMBDA_MIN);
+        }
+        let pdf = [1.0 / range; N_SAMPLES];
+
+        Self { lambda, pdf }
+    }
+
+    pub fn hero(&self) -> f64 {
+        self.lambda[0]
+    }
+
+    pub fn secondary_terminated(&self) -> bool {
+        self.pdf[1..].iter().all(|pdf| *pdf == 0.0)
+    }
+
+    // Keep only the hero wavelength, which now stands for all the others.
+    pub fn terminate_secondary(&mut self) {
+        if self.secondary_terminated() {
+            return;
+        }
+        for pdf in self.pdf[1..].iter_mut() {
+            *pdf = 0.0;
+        }
+        self.pdf[0] /= N_SAMPLES as f64;
+    }
+
+    // Upsample an RGB reflectance to the sampled wavelengths.
+    pub fn spectrum_from_rgb(&self, rgb: &Color) -> SampledSpectrum {
+        let mut values = [0.0; N_SAMPLES];
+        for (value, lambda) in values.iter_mut().zip(self.lambda.iter()) {
+            *value = rgb_to_spectrum(rgb, *lambda);
+        }
+
+        SampledSpectrum { values }
+    }
+
+    // Divide each radiance sample by the probability of its wavelength. Terminated wavelengths
+    // contribute nothing.
+    pub fn weight(&self, radiance: &SampledSpectrum) -> SampledSpectrum {
+        let mut values = [0.0; N_SAMPLES];
+        for (i, value) in values.iter_mut().enumerate() {
+            if self.pdf[i] != 0.0 {
+                *value = radiance.values[i] / self.pdf[i];
+            }
+        }
+
+        SampledSpectrum { values }
+    }
+
+    // Monte Carlo estimate of the CIE XYZ color of the weighted radiance (see weight()), converted
+    // to linear sRGB.
+    pub fn radiance_to_rgb(&self, weighted_radiance: &SampledSpectrum) -> Color {
+        let mut xyz = [0.0; 3];
+        for (lambda, value) in self.lambda.iter().zip(weighted_radiance.values.iter()) {
+            let cmf = cie_xyz(*lambda);
+            for (c, m) in xyz.iter_mut().zip(cmf.iter()) {
+                *c += value * m;
+            }
+        }
+        let scale = 1.0 / (N_SAMPLES as f64 * cie_y_integral());
+        let rgb = xyz_to_linear_srgb(&[xyz[0] * scale, xyz[1] * scale, xyz[2] * scale]);
+
+        rgb * white_balance()
+    }
+}
+
+// Radiance or reflectance sampled at the path wavelengths
+#[derive(Clone, Copy, Debug)]
+pub struct SampledSpectrum {
+    values: [f64; N_SAMPLES],
+}
+
+impl SampledSpectrum {
+    pub fn new(value: f64) -> Self {
+        let values = [value; N_SAMPLES];
+        Self { values }
+    }
+}
+
+impl Add for SampledSpectrum {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let mut values = self.values;
+        for (value, other) in values.iter_mut().zip(other.values.iter()) {
+            *value += other;
+        }
+
+        Self { values }
+    }
+}
+
+impl Mul for SampledSpectrum {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let mut values = self.values;
+        for (value, other) in values.iter_mut().zip(other.values.iter()) {
+            *value *= other;
+        }
+
+        Self { values }
+    }
+}
+
+impl MulAssign for SampledSpectrum {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+// RGB to spectrum
+//
+// Smits, "An RGB-to-Spectrum Conversion for Reflectances", 1999. Any RGB color is the sum of a
+// white spectrum and, at most, one of cyan/magenta/yellow and one of red/green/blue.
+const SMITS_LAMBDA_MIN: f64 = 380.0;
+const SMITS_LAMBDA_MAX: f64 = 720.0;
+const SMITS_WHITE: [f64; 10] = [
+    1.0000, 1.0000, 0.9999, 0.9993, 0.9992, 0.9998, 1.0000, 1.0000, 1.0000, 1.0000,
+];
+const SMITS_CYAN: [f64; 10] = [
+    0.9710, 0.9426, 1.0007, 1.0007, 1.0007, 1.0007, 0.1564, 0.0000, 0.0000, 0.0000,
+];
+const SMITS_MAGENTA: [f64; 10] = [
+    1.0000, 1.0000, 0.9685, 0.2229, 0.0000, 0.0458, 0.8369, 1.0000, 1.0000, 0.9959,
+];
+const SMITS_YELLOW: [f64; 10] = [
+    0.0001, 0.0000, 0.1088, 0.6651, 1.0000, 1.0000, 0.9996, 0.9586, 0.9685, 0.9840,
+];
+const SMITS_RED: [f64; 10] = [
+    0.1012, 0.0515, 0.0000, 0.0000, 0.0000, 0.0000, 0.8325, 1.0149, 1.0149, 1.0149,
+];
+const SMITS_GREEN: [f64; 10] = [
+    0.0000, 0.0000, 0.0273, 0.7937, 1.0000, 0.9418, 0.1719, 0.0000, 0.0000, 0.0025,
+];
+const SMITS_BLUE: [f64; 10] = [
+    1.0000, 1.0000, 0.8916, 0.3323, 0.0000, 0.0000, 0.0003, 0.0369, 0.0483, 0.0496,
+];
+
+// Linear interpolation of a Smits basis spectrum, clamped outside its range.
+fn smits(table: &[f64; 10], lambda: f64) -> f64 {
+    let step = (SMITS_LAMBDA_MAX - SMITS_LAMBDA_MIN) / (table.len() - 1) as f64;
+    let x = ((lambda - SMITS_LAMBDA_MIN) / step).clamp(0.0, (table.len() - 1) as f64);
+    let i = (x as usize).min(table.len() - 2);
+    let t = x - i as f64;
+
+    t.mul_add(table[i + 1] - table[i], table[i])
+}
+
+fn rgb_to_spectrum(rgb: &Color, lambda: f64) -> f64 {
+    let (r, g, b) = (rgb.x(), rgb.y(), rgb.z());
+    let basis = |table| smits(table, lambda);
+
+    if r <= g && r <= b {
+        let mut value = r * basis(&SMITS_WHITE);
+        if g <= b {
+            value += (g - r) * basis(&SMITS_CYAN) + (b - g) * basis(&SMITS_BLUE);
+        } else {
+            value += (b - r) * basis(&SMITS_CYAN) + (g - b) * basis(&SMITS_GREEN);
+        }
+        value
+    } else if g <= r && g <= b {
+        let mut value = g * basis(&SMITS_WHITE);
+        if r <= b {
+            value += (r - g) * basis(&SMITS_MAGENTA) + (b - r) * basis(&SMITS_BLUE);
+        } else {
+            value += (b - g) * basis(&SMITS_MAGENTA) + (r - b) * basis(&SMITS_RED);
+        }
+        value
+    } else {
+        let mut value = b * basis(&SMITS_WHITE);
+        if r <= g {
+            value += (r - b) * basis(&SMITS_YELLOW) + (g - r) * basis(&SMITS_GREEN);
+        } else {
+            value += (g - b) * basis(&SMITS_YELLOW) + (r - g) * basis(&SMITS_RED);
+        }
+        value
+    }
+}
+
+// CIE 1931 color matching functions
+//
+// Multi-lobe Gaussian fit from Wyman, Sloan and Shirley, "Simple Analytic Approximations to the
+// CIE XYZ Color Matching Functions", JCGT 2013.
+fn cie_xyz(lambda: f64) -> [f64; 3] {
+    let g = |mu: f64, sigma_low: f64, sigma_high: f64| {
+        let sigma = match lambda < mu {
+            true => sigma_low,
+            false => sigma_high,
+        };
+        let t = (lambda - mu) / sigma;
+        (-0.5 * t * t).exp()
+    };
+
+    let x =
+        1.056 * g(599.8, 37.9, 31.0) + 0.362 * g(442.0, 16.0, 26.7) - 0.065 * g(501.1, 20.4, 26.2);
+    let y = 0.821 * g(568.8, 46.9, 40.5) + 0.286 * g(530.9, 16.3, 31.1);
+    let z = 1.217 * g(437.0, 11.8, 36.0) + 0.681 * g(459.0, 26.0, 13.8);
+
+    [x, y, z]
+}
+
+// Integral of the matching functions over the sampled range, by the midpoint rule.
+fn cie_integrals() -> &'static [f64; 3] {
+    static INTEGRALS: OnceLock<[f64; 3]> = OnceLock::new();
+    INTEGRALS.get_or_init(|| {
+        const STEPS: usize = 4000;
+        let step = (LAMBDA_MAX - LAMBDA_MIN) / STEPS as f64;
+        let mut integrals = [0.0; 3];
+        for i in 0..STEPS {
+            let cmf = cie_xyz(LAMBDA_MIN + (i as f64 + 0.5) * step);
+            for (integral, m) in integrals.iter_mut().zip(cmf.iter()) {
+                *integral += m * step;
+            }
+        }
+        integrals
+    })
+}
+
+fn cie_y_integral() -> f64 {
+    cie_integrals()[1]
+}
+
+fn xyz_to_linear_srgb(xyz: &[f64; 3]) -> Color {
+    let [x, y, z] = *xyz;
+    Color::new(
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.9692660 * x + 1.8760108 * y + 0.0415560 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    )
+}
+
+// Per channel scale that maps the equal energy spectrum to RGB white.
+fn white_balance() -> Color {
+    static WHITE_BALANCE: OnceLock<Color> = OnceLock::new();
+    *WHITE_BALANCE.get_or_init(|| {
+        let [x, y, z] = *cie_integrals();
+        let white = xyz_to_linear_srgb(&[x / y, 1.0, z / y]);
+        Color::new(1.0 / white.x(), 1.0 / white.y(), 1.0 / white.z())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::WHITE;
+    use crate::vec3::Length;
+
+    #[test]
+    fn test_white_is_flat() {
+        for i in 0..=40 {
+            let lambda = LAMBDA_MIN + 10.0 * i as f64;
+            let value = rgb_to_spectrum(&WHITE, lambda);
+            assert!((value - 1.0).abs() < 1e-3, "{lambda}: {value}");
+        }
+    }
+
+    #[test]
+    fn test_rgb_round_trip() {
+        // Average the estimator over stratified hero wavelengths
+        const STRATA: usize = 1000;
+        for rgb in [WHITE, Color::new(0.5, 0.5, 0.5), Color::new(0.8, 0.2, 0.1)] {
+            let mut estimate = Color::default();
+            for i in 0..STRATA {
+                let hero = (i as f64 + 0.5) / STRATA as f64;
+                let mut wavelengths = SampledWavelengths::sample();
+                for (j, lambda) in wavelengths.lambda.iter_mut().enumerate() {
+                    let offset = (hero + j as f64 / N_SAMPLES as f64).fract();
+                    *lambda = (LAMBDA_MAX - LAMBDA_MIN).mul_add(offset, LAMBDA_MIN);
+                }
+                let radiance = wavelengths.spectrum_from_rgb(&rgb);
+                estimate += wavelengths.radiance_to_rgb(&wavelengths.weight(&radiance));
+            }
+            estimate /= STRATA as f64;
+
+            // Grays must come back exactly, saturated colors keep their hue.
+            if rgb.x() == rgb.y() {
+                assert!((estimate - rgb).length() < 0.01, "{rgb:?} -> {estimate:?}");
+            } else {
+                assert!(estimate.x() > estimate.y() && estimate.y() > estimate.z());
+            }
+        }
+    }
+
+    #[test]
+    fn test_terminate_secondary() {
+        let mut wavelengths = SampledWavelengths::sample();
+        let radiance = SampledSpectrum::new(1.0);
+        let before: f64 = wavelengths.weight(&radiance).values.iter().sum();
+
+        wavelengths.terminate_secondary();
+        assert!(wavelengths.secondary_terminated());
+
+        // The hero wavelength carries the weight of all the terminated ones
+        let after = wavelengths.weight(&radiance).values;
+        assert_eq!(before, after[0]);
+        assert!(after[1..].iter().all(|value| *value == 0.0));
+    }
+}
@@ -1,16 +1,19 @@
+use std::f64::consts::PI;
 use std::ops::Range;
 
 use crate::hit_record::HitRecord;
 use crate::hittable::Hittable;
 use crate::material::Material;
+use crate::onb::Onb;
 use crate::point3::Point3;
 use crate::ray::Ray;
-use crate::vec3::{Dot, LengthSquared};
+use crate::vec3::{Dot, LengthSquared, MulAdd, Vec3};
 
 pub struct Sphere {
     center: Point3,
     radius: f64,
     material: Box<dyn Material>,
+    motion: Vec3, // Displacement of the center per unit of time
 }
 
 impl Sphere {
@@ -19,12 +22,34 @@ impl Sphere {
             center,
             radius,
             material,
+            motion: Vec3::default(),
         }
     }
+
+    // Sphere moving linearly from center at time 0 to center + motion at time 1.
+    pub fn moving(center: Point3, motion: Vec3, radius: f64, material: Box<dyn Material>) -> Self {
+        Self {
+            motion,
+            ..Self::new(center, radius, material)
+        }
+    }
+
+    fn center(&self, time: f64) -> Point3 {
+        self.motion.mul_add(time, self.center)
+    }
+
+    // Cosine of the half angle of the cone of directions from origin towards the sphere, at time
+    // 0. None from inside, where it's seen all around.
+    fn cos_theta_max(&self, origin: &Point3) -> Option<f64> {
+        let distance_squared = (self.center - origin).length_squared();
+        let sin2_theta_max = self.radius * self.radius / distance_squared;
+
+        (sin2_theta_max < 1.0).then(|| (1.0 - sin2_theta_max).sqrt())
+    }
 }
 
 impl Hittable for Sphere {
-    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord> {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
         // Given a sphere centered in C = (Cx, Cy, Cz) and radius r, the points in the sphere
         // P = (Px, Py, Pz):
         //   (Px -C//x)^2 + (Py - Cy)^2 + (Pz - Cz)^2 = r^2
@@ -42,7 +67,8 @@ impl Hittable for Sphere {
         //   r = (-(2*half_b) +- sqrt(4*(half_b^2 - a*c)) / (2*a)
         //   r = (-(2*half_b) +- 2*sqrt(half_b^2 - a*c)) / (2*a)
         //   r = (-half_b +- sqrt(half_b^2 - a*c)) / a
-        let oc = r.origin() - self.center;
+        let center = self.center(r.time());
+        let oc = r.origin() - center;
         let a = r.direction().length_squared();
         let half_b = oc.dot(r.direction());
         let c = self.radius.mul_add(-self.radius, oc.length_squared());
@@ -71,9 +97,80 @@ impl Hittable for Sphere {
         // Hit in range!
         let t = root;
         let p = r.at(t);
-        let outward_normal = (p - self.center) / self.radius;
-        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r);
+        let outward_normal = (p - center) / self.radius;
+
+        // Spherical UVs: u is the longitude from -x, v the latitude from -y
+        let theta = (-outward_normal.y()).acos();
+        let phi = (-outward_normal.z()).atan2(outward_normal.x()) + PI;
+        let (u, v) = (phi / (2.0 * PI), theta / PI);
+
+        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r).with_uv(u, v);
 
         Some(hit_record)
     }
+
+    // Uniform over the cone of directions towards the sphere, or over all the directions from
+    // inside. Moving spheres are sampled at time 0.
+    fn pdf_value(&self, origin: &Point3, direction: &Vec3) -> f64 {
+        let r = Ray::new(*origin, *direction);
+        if self.hit(&r, &(0.001..f64::INFINITY)).is_none() {
+            return 0.0;
+        }
+
+        let solid_angle = match self.cos_theta_max(origin) {
+            Some(cos_theta_max) => 2.0 * PI * (1.0 - cos_theta_max),
+            None => 4.0 * PI,
+        };
+
+        1.0 / solid_angle
+    }
+
+    fn random(&self, origin: &Point3) -> Option<Vec3> {
+        // Direction at cos(theta) = z around the z axis
+        let direction = |z: f64| {
+            let phi = 2.0 * PI * fastrand::f64();
+            let sin_theta = (1.0 - z * z).max(0.0).sqrt();
+            Vec3::new(phi.cos() * sin_theta, phi.sin() * sin_theta, z)
+        };
+
+        let Some(cos_theta_max) = self.cos_theta_max(origin) else {
+            return Some(direction(1.0 - 2.0 * fastrand::f64()));
+        };
+        let z = 1.0 + fastrand::f64() * (cos_theta_max - 1.0);
+
+        Some(Onb::new(&(self.center - origin)).to_world(&direction(z)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::hittable::tests::check_sampling;
+    use crate::lambertian::Lambertian;
+
+    fn sphere() -> Sphere {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        Sphere::new(Point3::new(0.0, 0.0, -3.0), 1.0, material)
+    }
+
+    #[test]
+    fn test_sampling() {
+        let sphere = sphere();
+        let origin = Point3::new(0.0, 0.0, 0.0);
+        check_sampling(&sphere, &origin);
+
+        // Uniform over the cone of directions towards it
+        let cos_theta_max = (1.0 - 1.0 / 9.0_f64).sqrt();
+        let expected = 1.0 / (2.0 * PI * (1.0 - cos_theta_max));
+        let direction = Vec3::new(0.1, 0.2, -1.0);
+        assert!((sphere.pdf_value(&origin, &direction) - expected).abs() < 1e-12);
+        assert_eq!(0.0, sphere.pdf_value(&origin, &Vec3::new(0.0, 0.0, 1.0)));
+
+        // From inside, uniform over all the directions
+        let center = Point3::new(0.0, 0.2, -3.0);
+        check_sampling(&sphere, &center);
+        let direction = Vec3::new(1.0, 2.0, 3.0);
+        assert!((sphere.pdf_value(&center, &direction) - 1.0 / (4.0 * PI)).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,110 @@
+use crate::camera::{look_at_basis, Camera};
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::thin_lens_camera::ThinLensCamera;
+use crate::vec3::{Length, Vec3};
+
+// Arrangement of the two views of a stereo pair in one image
+#[derive(Clone, Copy)]
+pub enum StereoLayout {
+    SideBySide, // Left eye on the left half
+    TopBottom,  // Left eye on the top half
+}
+
+pub enum Eye {
+    Left,
+    Right,
+}
+
+impl StereoLayout {
+    // Eye seeing the film at (s, t), and the coordinates on the film of that eye.
+    pub fn split(&self, s: f64, t: f64) -> (Eye, f64, f64) {
+        match self {
+            Self::SideBySide if s < 0.5 => (Eye::Left, 2.0 * s, t),
+            Self::SideBySide => (Eye::Right, 2.0 * s - 1.0, t),
+            Self::TopBottom if t >= 0.5 => (Eye::Left, s, 2.0 * t - 1.0),
+            Self::TopBottom => (Eye::Right, s, 2.0 * t),
+        }
+    }
+
+    // Aspect ratio of the view of each eye, out of the one of the whole film.
+    pub fn eye_aspect_ratio(&self, aspect_ratio: f64) -> f64 {
+        match self {
+            Self::SideBySide => aspect_ratio / 2.0,
+            Self::TopBottom => aspect_ratio * 2.0,
+        }
+    }
+}
+
+pub struct StereoRig {
+    pub interocular: f64, // Distance between the eyes
+    pub convergence: f64, // Distance at which both views meet, with zero parallax
+    pub layout: StereoLayout,
+}
+
+// Stereo pair of thin lens cameras
+//
+// The eyes sit interocular apart around look_from, and toe in to converge towards look_at at the
+// convergence distance, where they also focus.
+pub struct StereoCamera {
+    left: ThinLensCamera,
+    right: ThinLensCamera,
+    layout: StereoLayout,
+}
+
+impl StereoCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        vertical_fov: f64, // Vertical field-of-view in degrees
+        aspect_ratio: f64, // Of the whole film, with both views
+        aperture: f64,
+        rig: StereoRig,
+    ) -> Self {
+        let (u, _, w) = look_at_basis(&look_from, &look_at, &up);
+        let convergence_point = look_from - rig.convergence * w;
+        let aspect_ratio = rig.layout.eye_aspect_ratio(aspect_ratio);
+
+        let eye = |side: f64| {
+            let position = look_from + side * rig.interocular / 2.0 * u;
+            let focus_distance = (convergence_point - position).length();
+            ThinLensCamera::new(
+                position,
+                convergence_point,
+                up,
+                vertical_fov,
+                aspect_ratio,
+                aperture,
+                focus_distance,
+            )
+        };
+
+        Self {
+            left: eye(-1.0),
+            right: eye(1.0),
+            layout: rig.layout,
+        }
+    }
+}
+
+impl Camera for StereoCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        match self.layout.split(s, t) {
+            (Eye::Left, s, t) => self.left.get_ray(s, t),
+            (Eye::Right, s, t) => self.right.get_ray(s, t),
+        }
+    }
+
+    fn exposure(&self) -> f64 {
+        self.left.exposure()
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            left: self.left.with_shutter(open, close),
+            right: self.right.with_shutter(open, close),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::aperture::Aperture;
+use crate::camera::{look_at_basis, Camera, Shutter};
+use crate::hittable::Hittable;
+use crate::physical_camera::PhysicalParameters;
+use crate::point3::Point3;
+use crate::ray::Ray;
+use crate::vec3::{Dot, MulAdd, Unit, Vec3};
+
+// Perspective camera with a thin lens, focused at focus_distance, for depth of field.
+pub struct ThinLensCamera {
+    origin: Point3,
+    lower_left_corner: Point3,
+    horizontal: Vec3,
+    vertical: Vec3,
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+    lens_radius: f64,
+    focal_plane: Option<(Point3, Vec3)>, // Point and normal when tilted, see with_tilt()
+    aperture: Aperture,
+    cat_eye: f64, // Shift of the exit pupil towards the film corners
+    shutter: Shutter,
+    exposure: f64,
+}
+
+impl ThinLensCamera {
+    pub fn new(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        vertical_fov: f64, // Vertical field-of-view in degrees
+        aspect_ratio: f64,
+        aperture: f64,
+        focus_distance: f64,
+    ) -> Self {
+        let theta = vertical_fov.to_radians();
+        let h = (theta / 2_f64).tan();
+        let viewport_height: f64 = 2_f64 * h;
+        let viewport_width: f64 = aspect_ratio * viewport_height;
+
+        let (u, v, w) = look_at_basis(&look_from, &look_at, &up);
+
+        let origin: Point3 = look_from;
+        let horizontal: Vec3 = focus_distance * viewport_width * u;
+        let vertical: Vec3 = focus_distance * viewport_height * v;
+        let lower_left_corner: Vec3 = origin - (horizontal + vertical) / 2_f64 - focus_distance * w;
+
+        let lens_radius = aperture / 2_f64;
+
+        Self {
+            origin,
+            lower_left_corner,
+            horizontal,
+            vertical,
+            u,
+            v,
+            w,
+            lens_radius,
+            focal_plane: None,
+            aperture: Aperture::Circular,
+            cat_eye: 0.0,
+            shutter: Shutter::default(),
+            exposure: 1.0,
+        }
+    }
+
+    // Camera described in photographic terms. The field of view follows from the lens and the
+    // sensor, the depth of field from the f-stop, and the shutter speed and ISO set the
+    // exposure. The shutter opens at time 0, times are in seconds.
+    pub fn physical(
+        look_from: Point3,
+        look_at: Point3,
+        up: Vec3,
+        aspect_ratio: f64,
+        focus_distance: f64,
+        parameters: &PhysicalParameters,
+    ) -> Self {
+        let camera = Self::new(
+            look_from,
+            look_at,
+            up,
+            parameters.vertical_fov(aspect_ratio),
+            aspect_ratio,
+            parameters.aperture(),
+            focus_distance,
+        );
+
+        camera
+            .with_shutter(0.0, parameters.shutter_speed)
+            .with_exposure(parameters.exposure())
+    }
+
+    // Lens shift: moves the image off the view axis, by fractions of its width and height, to
+    // frame without turning the camera. Tall buildings stay vertical when the camera is kept
+    // level and the image shifted up, instead of tilted up.
+    pub fn with_shift(self, horizontal: f64, vertical: f64) -> Self {
+        let lower_left_corner = self.horizontal.mul_add(
+            horizontal,
+            self.vertical.mul_add(vertical, self.lower_left_corner),
+        );
+
+        Self {
+            lower_left_corner,
+            ..self
+        }
+    }
+
+    // Lens tilt: the focal plane turns around the focus point (Scheimpflug principle), instead of
+    // facing the camera. A positive tilt leans its top away from the camera, down to the ground
+    // at 90 degrees, and a positive swing leans its right side away. A negative tilt makes a narrow
+    // band sharp and everything else blurry, the miniature effect.
+    pub fn with_tilt(self, tilt: f64, swing: f64) -> Self {
+        let (tilt, swing) = (tilt.to_radians(), swing.to_radians());
+        let normal = tilt.cos() * swing.cos() * self.w
+            + tilt.sin() * self.v
+            + tilt.cos() * swing.sin() * self.u;
+        let center = self
+            .horizontal
+            .mul_add(0.5, self.vertical.mul_add(0.5, self.lower_left_corner));
+        let focus_point = self.origin + (center - self.origin).dot(self.w) * self.w;
+
+        Self {
+            focal_plane: Some((focus_point, normal.unit())),
+            ..self
+        }
+    }
+
+    // Move the focus to target, like the center of an object. With a tilted focal plane, the
+    // plane goes through it keeping its tilt. The focus stays where it was if the plane would
+    // cross the view axis behind the camera, or never.
+    pub fn with_focus_on(self, target: &Point3) -> Self {
+        let normal = self.focal_plane.map_or(self.w, |(_, normal)| normal);
+        let focus_distance = (self.origin - target).dot(normal) / self.w.dot(normal);
+        if !(focus_distance.is_finite() && focus_distance > 0.0) {
+            return self;
+        }
+        let scale = focus_distance / self.focus_distance();
+
+        Self {
+            lower_left_corner: (self.lower_left_corner - self.origin).mul_add(scale, self.origin),
+            horizontal: scale * self.horizontal,
+            vertical: scale * self.vertical,
+            focal_plane: self
+                .focal_plane
+                .map(|(point, normal)| ((point - self.origin).mul_add(scale, self.origin), normal)),
+            ..self
+        }
+    }
+
+    // Focus on whatever of world is seen at the pixel (x, y) of the image, from its top left
+    // corner, like a click on it. The focus stays where it was if there is nothing.
+    pub fn with_autofocus(
+        self,
+        world: &dyn Hittable,
+        image_width: usize,
+        image_height: usize,
+        x: usize,
+        y: usize,
+    ) -> Self {
+        // Center of the pixel, on the film as rendered by rtx()
+        let s = (x as f64 + 0.5) / (image_width as f64 - 1.0);
+        let t = ((image_height - 1 - y) as f64 + 0.5) / (image_height as f64 - 1.0);
+        let target = self
+            .horizontal
+            .mul_add(s, self.vertical.mul_add(t, self.lower_left_corner));
+        let probe = Ray::new(self.origin, target - self.origin);
+        match world.hit(&probe, &(0.001..f64::INFINITY)) {
+            Some(hit_record) => self.with_focus_on(&hit_record.p()),
+            None => self,
+        }
+    }
+
+    // Distance from the lens to the focal plane, along the view axis
+    fn focus_distance(&self) -> f64 {
+        (self.origin - self.lower_left_corner).dot(self.w)
+    }
+
+    // Scale from radiance to pixel values, see physical()
+    pub fn with_exposure(self, exposure: f64) -> Self {
+        Self { exposure, ..self }
+    }
+
+    pub fn with_aperture(self, aperture: Aperture) -> Self {
+        Self { aperture, ..self }
+    }
+
+    // Mechanical vignetting: off axis, the lens barrel clips the opening into a cat's eye shape
+    // and darkens the corners. The pupil is shifted by strength at the film edges, 0 is none.
+    pub fn with_cat_eye(self, strength: f64) -> Self {
+        Self {
+            cat_eye: strength,
+            ..self
+        }
+    }
+}
+
+impl Camera for ThinLensCamera {
+    fn get_ray(&self, s: f64, t: f64) -> Option<Ray> {
+        let (x, y) = self.aperture.sample();
+        if self.cat_eye > 0.0 {
+            // The lens sample must also go through the shifted exit pupil
+            let (dx, dy) = (
+                x - self.cat_eye * (2.0 * s - 1.0),
+                y - self.cat_eye * (2.0 * t - 1.0),
+            );
+            if dx * dx + dy * dy > 1.0 {
+                return None;
+            }
+        }
+        let rd = self.lens_radius * Vec3::new(x, y, 0.0);
+
+        // ray_origin = self.origin + offset
+        // offset = self.u * rd.x() + self.v * rd.y()
+        // ray_origin = self.origin + self.u * rd.x() + self.v * rd.y()
+        //
+        let origin = self.u.mul_add(rd.x(), self.v.mul_add(rd.y(), self.origin));
+
+        // direction: self.lower_left_corner + s * self.horizontal + t * self.vertical - origin
+        //
+        let target = self
+            .horizontal
+            .mul_add(s, self.vertical.mul_add(t, self.lower_left_corner));
+        let direction = match &self.focal_plane {
+            None => target - origin,
+            Some((focus_point, normal)) => {
+                // All the rays through the lens converge on the point of the tilted focal plane
+                // seen through its center, or at infinity where the plane isn't in front.
+                let center_ray = target - self.origin;
+                let distance = (focus_point - self.origin).dot(normal) / center_ray.dot(normal);
+                match distance > 0.0 {
+                    true => center_ray.mul_add(distance, self.origin) - origin,
+                    false => center_ray,
+                }
+            }
+        };
+
+        Some(Ray::new(origin, direction).with_time(self.shutter.sample()))
+    }
+
+    fn exposure(&self) -> f64 {
+        self.exposure
+    }
+
+    fn with_shutter(self, open: f64, close: f64) -> Self {
+        Self {
+            shutter: Shutter::new(open, close),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::hittable_list::HittableList;
+    use crate::lambertian::Lambertian;
+    use crate::sphere::Sphere;
+
+    fn camera() -> ThinLensCamera {
+        ThinLensCamera::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            40.0,
+            1.0,
+            0.5,
+            10.0,
+        )
+    }
+
+    #[test]
+    fn test_focus_on() {
+        let camera = camera().with_focus_on(&Point3::new(1.0, 2.0, -4.0));
+        assert!((camera.focus_distance() - 4.0).abs() < 1e-12);
+
+        // Behind the camera, the focus stays
+        let camera = camera.with_focus_on(&Point3::new(0.0, 0.0, 3.0));
+        assert!((camera.focus_distance() - 4.0).abs() < 1e-12);
+
+        // A focal plane along the view axis never crosses it
+        let along_axis = ThinLensCamera {
+            focal_plane: Some((Point3::new(0.0, 0.0, -4.0), Vec3::new(0.0, 1.0, 0.0))),
+            ..camera
+        };
+        let along_axis = along_axis.with_focus_on(&Point3::new(0.0, -1.0, -2.0));
+        assert!((along_axis.focus_distance() - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_autofocus() {
+        let mut world = HittableList::default();
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        world.add(Box::new(Sphere::new(
+            Point3::new(0.0, 0.0, -6.0),
+            1.0,
+            material,
+        )));
+
+        // On the front of the ball, at the pixel in the middle of the image
+        let camera = camera().with_autofocus(&world, 102, 102, 50, 51);
+        assert!((camera.focus_distance() - 5.0).abs() < 1e-9);
+
+        // Nothing in the top left corner, nor in the bottom right one
+        let camera = camera.with_autofocus(&world, 102, 102, 0, 0);
+        assert!((camera.focus_distance() - 5.0).abs() < 1e-9);
+        let camera = camera.with_autofocus(&world, 102, 102, 101, 101);
+        assert!((camera.focus_distance() - 5.0).abs() < 1e-9);
+
+        // Off center, the ball is further away
+        let camera = camera.with_autofocus(&world, 102, 102, 50, 45);
+        assert!(camera.focus_distance() > 5.0);
+        assert!(camera.focus_distance() < 6.0);
+    }
+}
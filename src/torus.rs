@@ -0,0 +1,121 @@
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::hit_record::HitRecord;
+use crate::hittable::Hittable;
+use crate::material::Material;
+use crate::point3::Point3;
+use crate::polynomial::solve_quartic;
+use crate::ray::Ray;
+use crate::vec3::{Dot, Length, LengthSquared, Unit, Vec3};
+
+// Torus around center, with +y as its axis of revolution.
+pub struct Torus {
+    center: Point3,
+    major_radius: f64, // From the center to the center of the tube
+    minor_radius: f64, // Radius of the tube
+    material: Box<dyn Material>,
+}
+
+impl Torus {
+    pub fn new(
+        center: Point3,
+        major_radius: f64,
+        minor_radius: f64,
+        material: Box<dyn Material>,
+    ) -> Self {
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+}
+
+impl Hittable for Torus {
+    fn hit(&self, r: &Ray, t_range: &Range<f64>) -> Option<HitRecord<'_>> {
+        // Points of the torus P = (x, y, z):
+        //   (x^2 + y^2 + z^2 + R^2 - r^2)^2 = 4 * R^2 * (x^2 + z^2)
+        //
+        // with P being a ray, P(t) = A + B*t, is a quartic equation in t. The direction is
+        // normalized to keep the coefficients well conditioned.
+        let length = r.direction().length();
+        let o = r.origin() - self.center;
+        let d = r.direction() / length;
+
+        let r2 = self.minor_radius * self.minor_radius;
+        let four_major2 = 4.0 * self.major_radius * self.major_radius;
+        let e = o.length_squared() - self.major_radius * self.major_radius - r2;
+        let f = o.dot(d);
+
+        let coefficients = [
+            e * e - four_major2 * (r2 - o.y() * o.y()),
+            4.0 * f * e + 2.0 * four_major2 * o.y() * d.y(),
+            2.0 * e + 4.0 * f * f + four_major2 * d.y() * d.y(),
+            4.0 * f,
+            1.0,
+        ];
+
+        let t = solve_quartic(&coefficients)
+            .into_iter()
+            .map(|t| t / length)
+            .filter(|t| t_range.contains(t))
+            .min_by(|a, b| a.total_cmp(b))?;
+
+        // Gradient of the implicit surface
+        let p = r.at(t);
+        let local = p - self.center;
+        let sum = local.length_squared() - self.major_radius * self.major_radius - r2;
+        let outward_normal = Vec3::new(
+            local.x() * sum,
+            local.y() * (sum + four_major2 / 2.0),
+            local.z() * sum,
+        )
+        .unit();
+
+        // UVs: u goes around the axis and v around the tube
+        let u = (local.z().atan2(local.x()) + PI) / (2.0 * PI);
+        let tube = local.x().hypot(local.z()) - self.major_radius;
+        let v = (local.y().atan2(tube) + PI) / (2.0 * PI);
+        let hit_record = HitRecord::new(p, outward_normal, &*self.material, t, r).with_uv(u, v);
+
+        Some(hit_record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::lambertian::Lambertian;
+
+    #[test]
+    fn test_hit() {
+        let material = Box::new(Lambertian::new(Color::new(0.5, 0.5, 0.5)));
+        let torus = Torus::new(Point3::new(0.0, 0.0, 0.0), 2.0, 0.5, material);
+        let t_range = 0.001..f64::INFINITY;
+        let hit = |origin: Point3, direction: Vec3| {
+            let hit_record = torus.hit(&Ray::new(origin, direction), &t_range)?;
+            Some((hit_record.t(), hit_record.outward_normal()))
+        };
+        let assert_hit = |hit: Option<(f64, Vec3)>, t: f64, normal: Vec3| {
+            let (hit_t, hit_normal) = hit.unwrap();
+            assert!((hit_t - t).abs() < 1e-9, "{hit_t}");
+            assert!((hit_normal - normal).length() < 1e-9, "{hit_normal:?}");
+        };
+
+        // Along the x axis, with a direction that isn't normalized
+        let outside = hit(Point3::new(5.0, 0.0, 0.0), Vec3::new(-2.0, 0.0, 0.0));
+        assert_hit(outside, 1.25, Vec3::new(1.0, 0.0, 0.0));
+
+        // From the top, and from inside the tube
+        let top = hit(Point3::new(0.0, 5.0, 2.0), Vec3::new(0.0, -1.0, 0.0));
+        assert_hit(top, 4.5, Vec3::new(0.0, 1.0, 0.0));
+        let inside = hit(Point3::new(2.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert_hit(inside, 0.5, Vec3::new(1.0, 0.0, 0.0));
+
+        // Through the hole
+        assert!(hit(Point3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)).is_none());
+    }
+}
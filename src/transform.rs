@@ -0,0 +1,105 @@
+use crate::mat4::Mat4;
+use crate::point3::Point3;
+use crate::vec3::{Unit, Vec3};
+
+// Affine transform, with its inverse cached.
+#[derive(Clone, Debug, Default)]
+pub struct Transform {
+    matrix: Mat4,
+    inverse: Mat4,
+}
+
+impl Transform {
+    pub fn new(matrix: Mat4) -> Option<Self> {
+        let inverse = matrix.inverse()?;
+        Some(Self { matrix, inverse })
+    }
+
+    pub fn translation(offset: &Vec3) -> Self {
+        let matrix = Mat4::new([
+            [1.0, 0.0, 0.0, offset.x()],
+            [0.0, 1.0, 0.0, offset.y()],
+            [0.0, 0.0, 1.0, offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let inverse = Mat4::new([
+            [1.0, 0.0, 0.0, -offset.x()],
+            [0.0, 1.0, 0.0, -offset.y()],
+            [0.0, 0.0, 1.0, -offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        Self { matrix, inverse }
+    }
+
+    // Non uniform scaling. None if any of the factors is zero.
+    pub fn scaling(factors: &Vec3) -> Option<Self> {
+        Self::new(Mat4::new([
+            [factors.x(), 0.0, 0.0, 0.0],
+            [0.0, factors.y(), 0.0, 0.0],
+            [0.0, 0.0, factors.z(), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]))
+    }
+
+    // Counterclockwise rotation around axis, in degrees. Rodrigues' rotation formula.
+    pub fn rotation(axis: &Vec3, degrees: f64) -> Self {
+        let a = axis.unit();
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let (x, y, z) = (a.x(), a.y(), a.z());
+        let matrix = Mat4::new([
+            [
+                x * x + (1.0 - x * x) * cos,
+                x * y * (1.0 - cos) - z * sin,
+                x * z * (1.0 - cos) + y * sin,
+                0.0,
+            ],
+            [
+                x * y * (1.0 - cos) + z * sin,
+                y * y + (1.0 - y * y) * cos,
+                y * z * (1.0 - cos) - x * sin,
+                0.0,
+            ],
+            [
+                x * z * (1.0 - cos) - y * sin,
+                y * z * (1.0 - cos) + x * sin,
+                z * z + (1.0 - z * z) * cos,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        // Rotations are orthogonal
+        let inverse = matrix.transpose();
+
+        Self { matrix, inverse }
+    }
+
+    // Apply self and then other
+    pub fn then(&self, other: &Self) -> Self {
+        let matrix = other.matrix * self.matrix;
+        let inverse = self.inverse * other.inverse;
+
+        Self { matrix, inverse }
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            matrix: self.inverse,
+            inverse: self.matrix,
+        }
+    }
+
+    pub fn point(&self, p: &Point3) -> Point3 {
+        self.matrix.transform_point(p)
+    }
+
+    pub fn vector(&self, v: &Vec3) -> Vec3 {
+        self.matrix.transform_vector(v)
+    }
+
+    // Normals are transformed by the inverse transpose, to stay perpendicular to the surface.
+    pub fn normal(&self, n: &Vec3) -> Vec3 {
+        self.inverse.transpose().transform_vector(n).unit()
+    }
+}
@@ -245,6 +245,7 @@ pub trait Zero {
 
 // NearZero
 //
+#[allow(dead_code)]
 pub trait NearZero {
     fn is_near_zero(&self) -> bool;
 }